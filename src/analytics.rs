@@ -0,0 +1,4 @@
+pub mod histogram;
+pub mod history;
+pub mod jobs;
+pub mod leaderboard;
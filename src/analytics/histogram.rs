@@ -0,0 +1,188 @@
+use crate::models::SalaryHistogram;
+use serde::{Deserialize, Serialize};
+
+/// A salary range and the number of vacancies in it.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SalaryBucket {
+    /// The lowest salary counted in this bucket.
+    pub lower: f64,
+    /// The lowest salary of the next bucket, or `None` for the last bucket.
+    pub upper: Option<f64>,
+    /// The number of live job ads with a salary in range.
+    pub vacancies: usize,
+}
+
+/// Salary percentiles, in the local currency.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Percentiles {
+    pub p10: f64,
+    pub p25: f64,
+    pub p50: f64,
+    pub p75: f64,
+    pub p90: f64,
+}
+
+/// A salary histogram with its buckets parsed and sorted, to compute statistics from.
+///
+/// Salaries are assumed to be spread evenly within each bucket. The last bucket has no upper bound,
+/// so it is assumed to be as wide as the one before it.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SalaryDistribution {
+    buckets: Vec<SalaryBucket>,
+}
+
+impl SalaryDistribution {
+    /// Build a distribution from `(lower bound, vacancies)` pairs, in any order.
+    pub fn new(buckets: impl IntoIterator<Item = (f64, usize)>) -> Self {
+        let mut bounds: Vec<(f64, usize)> = buckets
+            .into_iter()
+            .filter(|(lower, _)| lower.is_finite())
+            .collect();
+        bounds.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let buckets = bounds
+            .iter()
+            .enumerate()
+            .map(|(i, &(lower, vacancies))| SalaryBucket {
+                lower,
+                upper: bounds.get(i + 1).map(|next| next.0),
+                vacancies,
+            })
+            .collect();
+        Self { buckets }
+    }
+
+    /// Parse the buckets of a histogram response. Keys which are not numbers are ignored.
+    pub fn from_histogram(histogram: &SalaryHistogram) -> Self {
+        let buckets = histogram
+            .histogram
+            .iter()
+            .flatten()
+            .filter_map(|(lower, vacancies)| {
+                lower
+                    .trim()
+                    .parse::<f64>()
+                    .ok()
+                    .map(|lower| (lower, *vacancies))
+            });
+        Self::new(buckets)
+    }
+
+    /// The buckets, from the lowest salaries to the highest.
+    pub fn buckets(&self) -> &[SalaryBucket] {
+        &self.buckets
+    }
+
+    /// The total number of vacancies.
+    pub fn total(&self) -> usize {
+        self.buckets.iter().map(|bucket| bucket.vacancies).sum()
+    }
+
+    /// The estimated mean salary, using the midpoint of each bucket.
+    pub fn mean(&self) -> Option<f64> {
+        let total = self.total();
+        if total == 0 {
+            return None;
+        }
+        let sum: f64 = (0..self.buckets.len())
+            .map(|i| {
+                let (lower, upper) = self.bounds(i);
+                (lower + upper) / 2.0 * self.buckets[i].vacancies as f64
+            })
+            .sum();
+        Some(sum / total as f64)
+    }
+
+    /// The estimated median salary.
+    pub fn median(&self) -> Option<f64> {
+        self.percentile(50.0)
+    }
+
+    /// The estimated salary below which `percentile` percent of the vacancies fall, for `percentile` between 0 and 100.
+    pub fn percentile(&self, percentile: f64) -> Option<f64> {
+        let total = self.total();
+        if total == 0 || !(0.0..=100.0).contains(&percentile) {
+            return None;
+        }
+        let target = percentile / 100.0 * total as f64;
+        let mut below = 0.0;
+        for (i, bucket) in self.buckets.iter().enumerate() {
+            let vacancies = bucket.vacancies as f64;
+            if vacancies > 0.0 && below + vacancies >= target {
+                let (lower, upper) = self.bounds(i);
+                return Some(lower + (target - below) / vacancies * (upper - lower));
+            }
+            below += vacancies;
+        }
+        self.buckets
+            .last()
+            .map(|_| self.bounds(self.buckets.len() - 1).1)
+    }
+
+    /// The 10th, 25th, 50th, 75th and 90th percentiles.
+    pub fn percentiles(&self) -> Option<Percentiles> {
+        Some(Percentiles {
+            p10: self.percentile(10.0)?,
+            p25: self.percentile(25.0)?,
+            p50: self.percentile(50.0)?,
+            p75: self.percentile(75.0)?,
+            p90: self.percentile(90.0)?,
+        })
+    }
+
+    /// The bucket with the most vacancies. Ties go to the lowest salaries.
+    pub fn mode(&self) -> Option<&SalaryBucket> {
+        self.buckets
+            .iter()
+            .filter(|bucket| bucket.vacancies > 0)
+            .reduce(|best, bucket| {
+                if bucket.vacancies > best.vacancies {
+                    bucket
+                } else {
+                    best
+                }
+            })
+    }
+
+    /// The estimated percentage of vacancies paying less than `salary`, between 0 and 100.
+    /// This tells where an offer sits compared to the market.
+    pub fn percentile_rank(&self, salary: f64) -> Option<f64> {
+        let total = self.total();
+        if total == 0 {
+            return None;
+        }
+        let below: f64 = (0..self.buckets.len())
+            .map(|i| {
+                let (lower, upper) = self.bounds(i);
+                let vacancies = self.buckets[i].vacancies as f64;
+                if salary >= upper {
+                    vacancies
+                } else if salary <= lower {
+                    0.0
+                } else {
+                    vacancies * (salary - lower) / (upper - lower)
+                }
+            })
+            .sum();
+        Some(below / total as f64 * 100.0)
+    }
+
+    /// The lower and upper bound of a bucket, estimating the upper bound of the last one.
+    fn bounds(&self, index: usize) -> (f64, f64) {
+        let bucket = &self.buckets[index];
+        let upper = bucket.upper.unwrap_or_else(|| {
+            let width = match index {
+                0 => bucket.lower,
+                _ => bucket.lower - self.buckets[index - 1].lower,
+            };
+            bucket.lower + width
+        });
+        (bucket.lower, upper)
+    }
+}
+
+impl From<&SalaryHistogram> for SalaryDistribution {
+    fn from(histogram: &SalaryHistogram) -> Self {
+        Self::from_histogram(histogram)
+    }
+}
@@ -0,0 +1,279 @@
+use crate::models::HistoricalSalary;
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// A calendar month, as used for the keys of a HistoricalSalary, e.g. `2013-09`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct YearMonth {
+    pub year: i32,
+    /// The month, from 1 to 12.
+    pub month: u32,
+}
+
+impl YearMonth {
+    /// Create a month, or `None` if `month` is not between 1 and 12.
+    pub fn new(year: i32, month: u32) -> Option<Self> {
+        (1..=12).contains(&month).then_some(Self { year, month })
+    }
+
+    /// The month `months` after this one, or before it if negative.
+    pub fn add_months(&self, months: i32) -> Self {
+        let index = self.index() + months;
+        Self {
+            year: index.div_euclid(12),
+            month: index.rem_euclid(12) as u32 + 1,
+        }
+    }
+
+    /// The number of months from `earlier` to this month.
+    pub fn months_since(&self, earlier: &YearMonth) -> i32 {
+        self.index() - earlier.index()
+    }
+
+    fn index(&self) -> i32 {
+        self.year * 12 + self.month as i32 - 1
+    }
+}
+
+impl Display for YearMonth {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:04}-{:02}", self.year, self.month)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum YearMonthError {
+    /// The text is not of the form `YYYY-MM`.
+    Format,
+    /// The year is not a number.
+    InvalidYear,
+    /// The month is not a number between 1 and 12.
+    InvalidMonth,
+}
+
+impl Display for YearMonthError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            YearMonthError::Format => {
+                write!(f, "month is not of the form YYYY-MM")
+            }
+            YearMonthError::InvalidYear => {
+                write!(f, "invalid year")
+            }
+            YearMonthError::InvalidMonth => {
+                write!(f, "invalid month, expected 01 to 12")
+            }
+        }
+    }
+}
+
+impl std::error::Error for YearMonthError {}
+
+impl FromStr for YearMonth {
+    type Err = YearMonthError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (year, month) = s.trim().split_once('-').ok_or(YearMonthError::Format)?;
+        let year = year.parse().map_err(|_| YearMonthError::InvalidYear)?;
+        let month = month.parse().map_err(|_| YearMonthError::InvalidMonth)?;
+        Self::new(year, month).ok_or(YearMonthError::InvalidMonth)
+    }
+}
+
+/// The average salary in a month.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SalaryPoint {
+    pub month: YearMonth,
+    pub salary: f64,
+}
+
+/// The change in average salary between a month and an earlier one.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SalaryChange {
+    pub month: YearMonth,
+    /// The difference with the earlier month, in the local currency.
+    pub absolute: f64,
+    /// The difference relative to the earlier month, in percent.
+    pub percent: f64,
+}
+
+/// Average salaries over time, sorted by month.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SalaryTimeSeries {
+    points: Vec<SalaryPoint>,
+}
+
+impl SalaryTimeSeries {
+    /// Build a series from points in any order. When a month appears twice, the last salary wins.
+    pub fn new(points: impl IntoIterator<Item = SalaryPoint>) -> Self {
+        let mut points: Vec<SalaryPoint> = points
+            .into_iter()
+            .filter(|point| point.salary.is_finite())
+            .collect();
+        points.reverse();
+        points.sort_by_key(|point| point.month);
+        points.dedup_by_key(|point| point.month);
+        Self { points }
+    }
+
+    /// Parse the months of a history response. Keys which are not of the form `YYYY-MM` are ignored.
+    pub fn from_history(history: &HistoricalSalary) -> Self {
+        let points = history
+            .month
+            .iter()
+            .flatten()
+            .filter_map(|(month, salary)| {
+                Some(SalaryPoint {
+                    month: month.parse().ok()?,
+                    salary: *salary,
+                })
+            });
+        Self::new(points)
+    }
+
+    /// The points of the series, from the oldest month to the newest.
+    pub fn points(&self) -> &[SalaryPoint] {
+        &self.points
+    }
+
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// The average salary in a month, if known.
+    pub fn get(&self, month: YearMonth) -> Option<f64> {
+        self.points
+            .binary_search_by_key(&month, |point| point.month)
+            .ok()
+            .map(|i| self.points[i].salary)
+    }
+
+    /// The change from the previous month, for every month whose previous month is known.
+    pub fn month_over_month(&self) -> Vec<SalaryChange> {
+        self.changes(1)
+    }
+
+    /// The change from the same month a year earlier, for every month where it is known.
+    pub fn year_over_year(&self) -> Vec<SalaryChange> {
+        self.changes(12)
+    }
+
+    /// The average over the last `window` calendar months, for every month with at least one known salary in its window.
+    /// Missing months are left out of the averages rather than counted as zero.
+    pub fn rolling_average(&self, window: usize) -> Vec<SalaryPoint> {
+        if window == 0 {
+            return Vec::new();
+        }
+        self.points
+            .iter()
+            .map(|point| {
+                let salaries: Vec<f64> = self
+                    .points
+                    .iter()
+                    .filter(|other| {
+                        let age = point.month.months_since(&other.month);
+                        age >= 0 && (age as usize) < window
+                    })
+                    .map(|other| other.salary)
+                    .collect();
+                SalaryPoint {
+                    month: point.month,
+                    salary: salaries.iter().sum::<f64>() / salaries.len() as f64,
+                }
+            })
+            .collect()
+    }
+
+    /// The slope of the least squares line through the series, in currency per month.
+    /// At least two points are needed.
+    pub fn trend_slope(&self) -> Option<f64> {
+        self.regression().map(|(slope, _)| slope)
+    }
+
+    /// The months missing between the first and last month of the series.
+    pub fn gaps(&self) -> Vec<YearMonth> {
+        self.points
+            .windows(2)
+            .flat_map(|pair| {
+                let missing = pair[1].month.months_since(&pair[0].month) - 1;
+                (1..=missing).map(move |offset| pair[0].month.add_months(offset))
+            })
+            .collect()
+    }
+
+    /// Extend the linear trend over the `months` following the last month of the series.
+    pub fn forecast(&self, months: usize) -> Vec<SalaryPoint> {
+        let (slope, intercept) = match self.regression() {
+            Some(line) => line,
+            None => return Vec::new(),
+        };
+        let first = self.points[0].month;
+        let last = self.points[self.points.len() - 1].month;
+        (1..=months as i32)
+            .map(|offset| {
+                let month = last.add_months(offset);
+                SalaryPoint {
+                    month,
+                    salary: intercept + slope * month.months_since(&first) as f64,
+                }
+            })
+            .collect()
+    }
+
+    fn changes(&self, lag: i32) -> Vec<SalaryChange> {
+        self.points
+            .iter()
+            .filter_map(|point| {
+                let earlier = self.get(point.month.add_months(-lag))?;
+                let absolute = point.salary - earlier;
+                Some(SalaryChange {
+                    month: point.month,
+                    absolute,
+                    percent: if earlier == 0.0 {
+                        0.0
+                    } else {
+                        absolute / earlier * 100.0
+                    },
+                })
+            })
+            .collect()
+    }
+
+    /// The slope and intercept of the least squares line, with months counted from the first point.
+    fn regression(&self) -> Option<(f64, f64)> {
+        if self.points.len() < 2 {
+            return None;
+        }
+        let first = self.points[0].month;
+        let n = self.points.len() as f64;
+        let xs: Vec<f64> = self
+            .points
+            .iter()
+            .map(|point| point.month.months_since(&first) as f64)
+            .collect();
+        let mean_x = xs.iter().sum::<f64>() / n;
+        let mean_y = self.points.iter().map(|point| point.salary).sum::<f64>() / n;
+        let (covariance, variance) =
+            xs.iter()
+                .zip(&self.points)
+                .fold((0.0, 0.0), |(covariance, variance), (x, point)| {
+                    (
+                        covariance + (x - mean_x) * (point.salary - mean_y),
+                        variance + (x - mean_x).powi(2),
+                    )
+                });
+        let slope = covariance / variance;
+        Some((slope, mean_y - slope * mean_x))
+    }
+}
+
+impl From<&HistoricalSalary> for SalaryTimeSeries {
+    fn from(history: &HistoricalSalary) -> Self {
+        Self::from_history(history)
+    }
+}
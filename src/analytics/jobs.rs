@@ -0,0 +1,210 @@
+use crate::analytics::histogram::SalaryDistribution;
+use crate::models::{ContractTime, ContractType, Job};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// Summary statistics of the salaries of a set of jobs, in the local currency.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct SalarySummary {
+    /// The number of jobs with a salary.
+    pub count: usize,
+    pub min: f64,
+    pub max: f64,
+    pub mean: f64,
+    pub median: f64,
+}
+
+/// The jobs of one group in a breakdown.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct Group {
+    /// The number of jobs in the group.
+    pub jobs: usize,
+    /// The number of jobs in the group with a salary.
+    pub salaries: usize,
+    /// The mean salary of the jobs in the group, if any has a salary.
+    pub mean_salary: Option<f64>,
+}
+
+/// How posting dates are grouped in a histogram.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DateInterval {
+    /// Group by `YYYY-MM-DD`.
+    Day,
+    /// Group by `YYYY-MM`.
+    Month,
+    /// Group by `YYYY`.
+    Year,
+}
+
+impl DateInterval {
+    fn prefix_len(&self) -> usize {
+        match self {
+            DateInterval::Day => 10,
+            DateInterval::Month => 7,
+            DateInterval::Year => 4,
+        }
+    }
+}
+
+/// Aggregate statistics over a set of jobs, such as the results of one or more searches.
+///
+/// The salary of a job is the midpoint of `salary_min` and `salary_max`. Jobs without a positive
+/// salary are counted in breakdowns but left out of salary statistics.
+#[derive(Debug, Clone)]
+pub struct JobStats<'a> {
+    jobs: Vec<&'a Job>,
+    include_predicted: bool,
+}
+
+impl<'a> JobStats<'a> {
+    pub fn new(jobs: impl IntoIterator<Item = &'a Job>) -> Self {
+        Self {
+            jobs: jobs.into_iter().collect(),
+            include_predicted: true,
+        }
+    }
+
+    /// Whether salaries predicted by Adzuna are used in salary statistics. Defaults to `true`.
+    pub fn include_predicted(mut self, include_predicted: bool) -> Self {
+        self.include_predicted = include_predicted;
+        self
+    }
+
+    /// The number of jobs.
+    pub fn len(&self) -> usize {
+        self.jobs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.jobs.is_empty()
+    }
+
+    /// The salary of a job, if it has one and it should be counted.
+    fn salary(&self, job: &Job) -> Option<f64> {
+        if job.salary_is_predicted && !self.include_predicted {
+            return None;
+        }
+        let salary = match (job.salary_min > 0.0, job.salary_max > 0.0) {
+            (true, true) => (job.salary_min + job.salary_max) / 2.0,
+            (true, false) => job.salary_min,
+            (false, true) => job.salary_max,
+            (false, false) => return None,
+        };
+        salary.is_finite().then_some(salary)
+    }
+
+    /// The salaries of the jobs, sorted from lowest to highest.
+    pub fn salaries(&self) -> Vec<f64> {
+        let mut salaries: Vec<f64> = self
+            .jobs
+            .iter()
+            .filter_map(|job| self.salary(job))
+            .collect();
+        salaries.sort_by(f64::total_cmp);
+        salaries
+    }
+
+    /// Summary statistics of the salaries, or `None` if no job has a salary.
+    pub fn salary_summary(&self) -> Option<SalarySummary> {
+        let salaries = self.salaries();
+        let count = salaries.len();
+        if count == 0 {
+            return None;
+        }
+        let middle = count / 2;
+        let median = match count % 2 {
+            0 => (salaries[middle - 1] + salaries[middle]) / 2.0,
+            _ => salaries[middle],
+        };
+        Some(SalarySummary {
+            count,
+            min: salaries[0],
+            max: salaries[count - 1],
+            mean: salaries.iter().sum::<f64>() / count as f64,
+            median,
+        })
+    }
+
+    /// The salaries grouped in buckets of `bucket_width`, like the "histogram" endpoint.
+    /// The distribution is empty unless the width is a positive, finite number.
+    pub fn salary_distribution(&self, bucket_width: f64) -> SalaryDistribution {
+        if !bucket_width.is_finite() || bucket_width <= 0.0 {
+            return SalaryDistribution::default();
+        }
+        let mut buckets: BTreeMap<i64, usize> = BTreeMap::new();
+        for salary in self.salaries() {
+            *buckets
+                .entry((salary / bucket_width).floor() as i64)
+                .or_default() += 1;
+        }
+        SalaryDistribution::new(
+            buckets
+                .into_iter()
+                .map(|(bucket, vacancies)| (bucket as f64 * bucket_width, vacancies)),
+        )
+    }
+
+    /// Group the jobs by a key.
+    pub fn breakdown<K: Ord>(&self, key: impl Fn(&Job) -> K) -> BTreeMap<K, Group> {
+        let mut totals: BTreeMap<K, (Group, f64)> = BTreeMap::new();
+        for job in &self.jobs {
+            let (group, sum) = totals.entry(key(job)).or_default();
+            group.jobs += 1;
+            if let Some(salary) = self.salary(job) {
+                group.salaries += 1;
+                *sum += salary;
+            }
+        }
+        totals
+            .into_iter()
+            .map(|(key, (mut group, sum))| {
+                group.mean_salary = (group.salaries > 0).then(|| sum / group.salaries as f64);
+                (key, group)
+            })
+            .collect()
+    }
+
+    /// Group the jobs by contract type, `None` when it is not given.
+    pub fn by_contract_type(&self) -> BTreeMap<Option<ContractType>, Group> {
+        self.breakdown(|job| job.contract_type)
+    }
+
+    /// Group the jobs by contract time, `None` when it is not given.
+    pub fn by_contract_time(&self) -> BTreeMap<Option<ContractTime>, Group> {
+        self.breakdown(|job| job.contract_time)
+    }
+
+    /// Group the jobs by company display name, `None` when it is not given.
+    pub fn by_company(&self) -> BTreeMap<Option<String>, Group> {
+        self.breakdown(|job| job.company.display_name.clone())
+    }
+
+    /// Group the jobs by the location at a level of `location.area`, where 0 is the country.
+    /// Jobs with a less precise location are grouped under `None`.
+    pub fn by_location(&self, level: usize) -> BTreeMap<Option<String>, Group> {
+        self.breakdown(|job| {
+            job.location
+                .area
+                .as_ref()
+                .and_then(|area| area.get(level).cloned())
+        })
+    }
+
+    /// Group the jobs by category tag.
+    pub fn by_category(&self) -> BTreeMap<String, Group> {
+        self.breakdown(|job| job.category.tag.clone())
+    }
+
+    /// Count the jobs by posting date, using the start of their ISO 8601 `created` date.
+    pub fn posting_dates(&self, interval: DateInterval) -> BTreeMap<String, usize> {
+        let mut dates = BTreeMap::new();
+        for job in &self.jobs {
+            let date = job
+                .created
+                .get(..interval.prefix_len())
+                .unwrap_or(&job.created);
+            *dates.entry(date.to_string()).or_default() += 1;
+        }
+        dates
+    }
+}
@@ -0,0 +1,203 @@
+use crate::models::{Company, TopCompanies};
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// A company and its position in a leaderboard.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LeaderboardEntry {
+    /// The position in the leaderboard, starting at 1.
+    pub rank: usize,
+    /// The `canonical_name` of the company, or its display name when that is missing.
+    pub key: String,
+    pub company: Company,
+}
+
+/// A company present in both leaderboards of a diff.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RankChange {
+    pub key: String,
+    /// The company in the newer leaderboard.
+    pub company: Company,
+    pub old_rank: usize,
+    pub new_rank: usize,
+    /// The change in the number of advertisements, if both leaderboards have it.
+    pub count_delta: Option<i64>,
+    /// The change in the average salary, if both leaderboards have it.
+    pub average_salary_delta: Option<f64>,
+}
+
+impl RankChange {
+    /// How many places the company climbed. Negative when it fell.
+    pub fn movement(&self) -> i64 {
+        self.old_rank as i64 - self.new_rank as i64
+    }
+}
+
+/// The differences between an older and a newer leaderboard.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct LeaderboardDiff {
+    /// Companies in both leaderboards, in the order of the newer one.
+    pub changes: Vec<RankChange>,
+    /// Companies only in the newer leaderboard.
+    pub entrants: Vec<LeaderboardEntry>,
+    /// Companies only in the older leaderboard.
+    pub dropouts: Vec<LeaderboardEntry>,
+}
+
+/// A ranking of companies, such as the "top_companies" leaderboard, keyed by canonical name.
+///
+/// Companies with neither a canonical nor a display name cannot be compared and are left out.
+/// Only the first entry is kept when a company appears twice.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct Leaderboard {
+    entries: Vec<LeaderboardEntry>,
+}
+
+impl Leaderboard {
+    /// Build a leaderboard from companies, in rank order.
+    pub fn new(companies: impl IntoIterator<Item = Company>) -> Self {
+        let mut entries: Vec<LeaderboardEntry> = Vec::new();
+        for company in companies {
+            let Some(key) = Self::key(&company) else {
+                continue;
+            };
+            if entries.iter().any(|entry| entry.key == key) {
+                continue;
+            }
+            entries.push(LeaderboardEntry {
+                rank: entries.len() + 1,
+                key,
+                company,
+            });
+        }
+        Self { entries }
+    }
+
+    /// Build a leaderboard from the response of the "top_companies" endpoint.
+    pub fn from_top_companies(top_companies: &TopCompanies) -> Self {
+        Self::new(top_companies.leaderboard.iter().flatten().cloned())
+    }
+
+    fn key(company: &Company) -> Option<String> {
+        company.canonical_name.clone().or_else(|| {
+            company
+                .display_name
+                .as_ref()
+                .map(|name| name.to_lowercase())
+        })
+    }
+
+    pub fn entries(&self) -> &[LeaderboardEntry] {
+        &self.entries
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// The entry of a company, by canonical name.
+    pub fn get(&self, key: &str) -> Option<&LeaderboardEntry> {
+        self.entries.iter().find(|entry| entry.key == key)
+    }
+
+    /// Compare this leaderboard with a newer one, for instance from a later date or another keyword.
+    pub fn diff(&self, newer: &Leaderboard) -> LeaderboardDiff {
+        let mut diff = LeaderboardDiff::default();
+        for entry in &newer.entries {
+            let Some(old) = self.get(&entry.key) else {
+                diff.entrants.push(entry.clone());
+                continue;
+            };
+            let (before, after) = (&old.company, &entry.company);
+            diff.changes.push(RankChange {
+                key: entry.key.clone(),
+                company: after.clone(),
+                old_rank: old.rank,
+                new_rank: entry.rank,
+                count_delta: before
+                    .count
+                    .zip(after.count)
+                    .map(|(before, after)| after as i64 - before as i64),
+                average_salary_delta: before
+                    .average_salary
+                    .zip(after.average_salary)
+                    .map(|(before, after)| after - before),
+            });
+        }
+        diff.dropouts = self
+            .entries
+            .iter()
+            .filter(|entry| newer.get(&entry.key).is_none())
+            .cloned()
+            .collect();
+        diff
+    }
+
+    /// Merge leaderboards, for instance from several locations, into a single ranking.
+    ///
+    /// Counts are summed and average salaries are weighted by count. Companies are ranked by
+    /// their total count, with ties broken by their best rank in any of the leaderboards.
+    pub fn combine<'a>(leaderboards: impl IntoIterator<Item = &'a Leaderboard>) -> Self {
+        struct Total {
+            company: Company,
+            best_rank: usize,
+            count: Option<usize>,
+            salary_sum: f64,
+            salary_weight: f64,
+        }
+
+        let mut order: Vec<String> = Vec::new();
+        let mut totals: HashMap<String, Total> = HashMap::new();
+        for entry in leaderboards.into_iter().flat_map(|board| &board.entries) {
+            let total = totals.entry(entry.key.clone()).or_insert_with(|| {
+                order.push(entry.key.clone());
+                Total {
+                    company: entry.company.clone(),
+                    best_rank: entry.rank,
+                    count: None,
+                    salary_sum: 0.0,
+                    salary_weight: 0.0,
+                }
+            });
+            total.best_rank = total.best_rank.min(entry.rank);
+            if let Some(count) = entry.company.count {
+                total.count = Some(total.count.unwrap_or(0) + count);
+            }
+            if let Some(salary) = entry.company.average_salary {
+                let weight = entry.company.count.unwrap_or(1) as f64;
+                total.salary_sum += salary * weight;
+                total.salary_weight += weight;
+            }
+            if total.company.display_name.is_none() {
+                total.company.display_name = entry.company.display_name.clone();
+            }
+        }
+
+        let mut combined: Vec<Total> = order
+            .into_iter()
+            .filter_map(|key| totals.remove(&key))
+            .collect();
+        combined.sort_by(|a, b| {
+            b.count
+                .unwrap_or(0)
+                .cmp(&a.count.unwrap_or(0))
+                .then(a.best_rank.cmp(&b.best_rank))
+        });
+        Self::new(combined.into_iter().map(|total| Company {
+            count: total.count,
+            average_salary:
+                (total.salary_weight > 0.0).then(|| total.salary_sum / total.salary_weight),
+            ..total.company
+        }))
+    }
+}
+
+impl From<&TopCompanies> for Leaderboard {
+    fn from(top_companies: &TopCompanies) -> Self {
+        Self::from_top_companies(top_companies)
+    }
+}
@@ -0,0 +1,158 @@
+use crate::models;
+use crate::request::{Error, ErrorKind, RequestBuilder};
+use futures::future::BoxFuture;
+use futures::stream::{self, Stream, StreamExt};
+use serde::de::DeserializeOwned;
+use std::fmt::Debug;
+use std::time::Duration;
+
+/// A response from any endpoint, for batches mixing different kinds of requests.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AnyResponse {
+    Version(models::Version),
+    Categories(models::Categories),
+    Histogram(models::SalaryHistogram),
+    History(models::HistoricalSalary),
+    TopCompanies(models::TopCompanies),
+    Geodata(models::JobGeoData),
+    Search(models::JobSearchResults),
+}
+
+macro_rules! impl_any_response {
+    ($($variant: ident($response: ty)),*) => {
+        $(
+            impl From<$response> for AnyResponse {
+                fn from(response: $response) -> Self {
+                    AnyResponse::$variant(response)
+                }
+            }
+        )*
+    };
+}
+
+impl_any_response!(
+    Version(models::Version),
+    Categories(models::Categories),
+    Histogram(models::SalaryHistogram),
+    History(models::HistoricalSalary),
+    TopCompanies(models::TopCompanies),
+    Geodata(models::JobGeoData),
+    Search(models::JobSearchResults)
+);
+
+/// A type-erased request, as accepted by Batch::requests.
+pub type BoxedRequest<'a, T> = Box<dyn RequestBuilder<Response = T> + Send + Sync + 'a>;
+
+/// A list of requests executed together with bounded concurrency.
+///
+/// Requests of different kinds can be mixed by collecting their responses into AnyResponse.
+/// Each request is sent by the client it was built from, whose concurrency and rate limits
+/// still apply on top of the batch's own limit.
+pub struct Batch<'a, T> {
+    requests: Vec<BoxFuture<'a, Result<T, Error>>>,
+    concurrency: usize,
+    timeout: Option<Duration>,
+}
+
+impl<'a, T: Send + 'a> Default for Batch<'a, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, T: Send + 'a> Batch<'a, T> {
+    /// Create an empty batch. `Client::batch` is the usual way to start one.
+    pub fn new() -> Self {
+        Self {
+            requests: Vec::new(),
+            concurrency: 4,
+            timeout: None,
+        }
+    }
+
+    /// Set how many requests of the batch may run at once. Defaults to 4.
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        if concurrency > 0 {
+            self.concurrency = concurrency;
+        }
+        self
+    }
+
+    /// Give up on any single request which takes longer than `timeout`.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Add a request to the batch.
+    pub fn request<R>(mut self, request: R) -> Self
+    where
+        R: RequestBuilder + Send + Sync + 'a,
+        R::Response: Into<T>,
+    {
+        self.requests.push(Box::pin(
+            async move { request.fetch().await.map(Into::into) },
+        ));
+        self
+    }
+
+    /// Add a list of boxed requests to the batch.
+    pub fn requests<R>(mut self, requests: impl IntoIterator<Item = BoxedRequest<'a, R>>) -> Self
+    where
+        R: DeserializeOwned + Debug + Into<T> + 'a,
+    {
+        for request in requests {
+            self.requests.push(Box::pin(
+                async move { request.fetch().await.map(Into::into) },
+            ));
+        }
+        self
+    }
+
+    /// The number of requests in the batch.
+    pub fn len(&self) -> usize {
+        self.requests.len()
+    }
+
+    /// Whether the batch has no requests.
+    pub fn is_empty(&self) -> bool {
+        self.requests.is_empty()
+    }
+
+    /// Executes every request, returning the results in the order the requests were added.
+    pub async fn fetch(self) -> Vec<Result<T, Error>> {
+        self.stream().collect().await
+    }
+
+    /// Executes every request, yielding the results in the order the requests were added.
+    pub fn stream(self) -> impl Stream<Item = Result<T, Error>> + 'a {
+        let concurrency = self.concurrency;
+        self.into_futures()
+            .map(|(_, result)| result)
+            .buffered(concurrency)
+    }
+
+    /// Executes every request, yielding each result as soon as it completes
+    /// along with the position of its request in the batch.
+    pub fn stream_unordered(self) -> impl Stream<Item = (usize, Result<T, Error>)> + 'a {
+        let concurrency = self.concurrency;
+        self.into_futures()
+            .map(|(index, result)| async move { (index, result.await) })
+            .buffer_unordered(concurrency)
+    }
+
+    fn into_futures(self) -> impl Stream<Item = (usize, BoxFuture<'a, Result<T, Error>>)> + 'a {
+        let timeout = self.timeout;
+        stream::iter(self.requests.into_iter().enumerate()).map(move |(index, request)| {
+            let request: BoxFuture<'a, Result<T, Error>> = match timeout {
+                Some(timeout) => Box::pin(async move {
+                    tokio::time::timeout(timeout, request)
+                        .await
+                        .unwrap_or_else(|_| Err(Error::from_kind(ErrorKind::Timeout)))
+                }),
+                None => request,
+            };
+            (index, request)
+        })
+    }
+}
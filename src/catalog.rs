@@ -0,0 +1,103 @@
+use crate::client::Client;
+use crate::models::{Category, Country};
+use crate::request::{Error, RequestBuilder};
+use serde::{Deserialize, Serialize};
+
+/// The job categories available in a country, with lookups to validate tags before sending a request.
+///
+/// A catalog can be serialized and stored, so it does not have to be fetched on every run.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CategoryCatalog {
+    /// The country the categories belong to.
+    pub country: Country,
+    categories: Vec<Category>,
+}
+
+impl CategoryCatalog {
+    /// Create a catalog from categories obtained elsewhere.
+    pub fn new(country: Country, categories: Vec<Category>) -> Self {
+        Self {
+            country,
+            categories,
+        }
+    }
+
+    /// Load the catalog for a country from the categories endpoint.
+    pub async fn fetch(client: &Client, country: Country) -> Result<Self, Error> {
+        let categories = client.categories().country(country).fetch().await?;
+        Ok(Self::new(country, categories.results))
+    }
+
+    /// All the categories in this catalog.
+    pub fn categories(&self) -> &[Category] {
+        &self.categories
+    }
+
+    /// Look up a category by its exact tag.
+    pub fn get(&self, tag: &str) -> Option<&Category> {
+        self.categories.iter().find(|category| category.tag == tag)
+    }
+
+    /// Look up a category by its label, ignoring case.
+    pub fn find_label(&self, label: &str) -> Option<&Category> {
+        let label = label.trim().to_lowercase();
+        self.categories
+            .iter()
+            .find(|category| category.label.to_lowercase() == label)
+    }
+
+    /// Look up a category by tag, falling back to its label.
+    pub fn resolve(&self, query: &str) -> Option<&Category> {
+        self.get(query).or_else(|| self.find_label(query))
+    }
+
+    /// Suggest up to `limit` categories whose tag or label resembles `query`, closest first.
+    /// This is intended for correcting typos, so categories that are too different are left out.
+    pub fn suggest(&self, query: &str, limit: usize) -> Vec<&Category> {
+        let query = query.trim().to_lowercase();
+        let mut scored: Vec<(usize, &Category)> = self
+            .categories
+            .iter()
+            .filter_map(|category| {
+                let score = [&category.tag, &category.label]
+                    .iter()
+                    .map(|candidate| similarity(&query, &candidate.to_lowercase()))
+                    .min()?;
+                let threshold = (query.chars().count() / 3).max(2);
+                (score <= threshold).then_some((score, category))
+            })
+            .collect();
+        scored.sort_by_key(|(score, _)| *score);
+        scored
+            .into_iter()
+            .take(limit)
+            .map(|(_, category)| category)
+            .collect()
+    }
+}
+
+/// Distance between a query and a candidate, where a query contained in the candidate counts as an exact match.
+fn similarity(query: &str, candidate: &str) -> usize {
+    if query.is_empty() {
+        return usize::MAX;
+    }
+    if candidate.contains(query) {
+        return 0;
+    }
+    levenshtein(query, candidate)
+}
+
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut previous = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous + usize::from(ca != *cb);
+            previous = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(previous + 1);
+        }
+    }
+    row[b.len()]
+}
@@ -1,55 +1,296 @@
+use crate::batch::Batch;
+use crate::config::{ClientConfig, ConfigError, Defaults};
+use crate::crawler::LocationTreeCrawler;
+use crate::credentials::CredentialPool;
+use crate::middleware::Middleware;
+use crate::models::Country;
+use crate::profile::CompanyProfileBuilder;
+use crate::report::SalaryReportBuilder;
 use crate::request::*;
+use crate::transport::{HttpRequest, HttpResponse, HttpTransport, ReqwestTransport};
+use crate::usage::UsageStats;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::{Semaphore, SemaphorePermit};
+use tokio::time::Instant;
 
 /// The main client of the wrapper to access API routes.
 pub struct Client {
-    pub app_id: String,
-    pub app_key: String,
-    pub req_client: reqwest::Client,
+    credentials: CredentialPool,
+    base_url: String,
+    defaults: Defaults,
+    transport: Arc<dyn HttpTransport>,
+    custom_transport: bool,
+    middleware: Vec<Arc<dyn Middleware>>,
+    timeout: Option<Duration>,
+    concurrency: Option<Semaphore>,
+    min_interval: Option<Duration>,
+    next_slot: Mutex<Option<Instant>>,
+    usage: Mutex<UsageStats>,
 }
 
 impl Client {
     /// Create a new client with API credentials.
     pub fn new(app_id: String, app_key: String) -> Self {
-        let req_client = reqwest::Client::new();
+        Self::with_credentials(CredentialPool::single(app_id, app_key))
+    }
+
+    /// Create a new client sharing requests between several sets of API credentials.
+    pub fn with_credentials(credentials: CredentialPool) -> Self {
         Self {
-            app_id,
-            app_key,
-            req_client,
+            credentials,
+            base_url: ROOT_URL.into(),
+            defaults: Defaults::default(),
+            transport: Arc::new(ReqwestTransport::default()),
+            custom_transport: false,
+            middleware: Vec::new(),
+            timeout: None,
+            concurrency: None,
+            min_interval: None,
+            next_slot: Mutex::new(None),
+            usage: Mutex::new(UsageStats::default()),
+        }
+    }
+
+    /// Create a new client from the `ADZUNA_APP_ID` and `ADZUNA_APP_KEY` environment variables,
+    /// and the optional `ADZUNA_COUNTRY` and `ADZUNA_BASE_URL`. See ClientConfig::from_env.
+    pub fn from_env() -> Result<Self, ConfigError> {
+        ClientConfig::from_env()?.build()
+    }
+
+    /// Create a new client from a TOML or JSON configuration file.
+    pub fn from_config_file(path: impl AsRef<Path>) -> Result<Self, ConfigError> {
+        ClientConfig::from_file(path)?.build()
+    }
+
+    /// Send requests to another root URL, such as a proxy or a mock server.
+    pub fn base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').into();
+        self
+    }
+
+    /// The root URL requests are sent to.
+    pub fn get_base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Set the values every new request builder starts with, such as the country.
+    pub fn defaults(mut self, defaults: Defaults) -> Self {
+        self.defaults = defaults;
+        self
+    }
+
+    /// The values every new request builder starts with.
+    pub fn get_defaults(&self) -> &Defaults {
+        &self.defaults
+    }
+
+    /// Set the country requests are sent for when they do not specify one. Defaults to US.
+    pub fn default_country(mut self, country: Country) -> Self {
+        self.defaults.country = Some(country);
+        self
+    }
+
+    /// The country requests are sent for when they do not specify one.
+    pub fn get_default_country(&self) -> Country {
+        self.defaults.country.unwrap_or(Country::UnitedStates)
+    }
+
+    /// The credentials requests are authenticated with.
+    pub fn credentials(&self) -> &CredentialPool {
+        &self.credentials
+    }
+
+    /// Send requests through another transport, such as a StubTransport in tests.
+    /// The transport is used as given, so `connect_timeout` has no effect on it.
+    pub fn transport(mut self, transport: impl HttpTransport + 'static) -> Self {
+        self.transport = Arc::new(transport);
+        self.custom_transport = true;
+        self
+    }
+
+    /// The transport requests are sent through.
+    pub fn get_transport(&self) -> &dyn HttpTransport {
+        self.transport.as_ref()
+    }
+
+    /// Add a middleware layer which sees every request and response.
+    pub fn middleware(mut self, middleware: impl Middleware + 'static) -> Self {
+        self.middleware.push(Arc::new(middleware));
+        self
+    }
+
+    /// Send a request through the middleware chain and the transport.
+    pub(crate) async fn send(&self, mut request: HttpRequest) -> Result<HttpResponse, Error> {
+        for layer in &self.middleware {
+            layer.on_request(&mut request);
+        }
+        #[cfg(feature = "tracing")]
+        crate::trace::record_request(&request);
+        let started = Instant::now();
+        let sending = self.transport.send(request.clone());
+        let mut response = match request.timeout {
+            Some(timeout) => tokio::time::timeout(timeout, sending)
+                .await
+                .unwrap_or_else(|_| Err(Error::from_kind(ErrorKind::Timeout))),
+            None => sending.await,
+        };
+        let elapsed = started.elapsed();
+        for layer in self.middleware.iter().rev() {
+            layer.on_response(&request, &mut response, elapsed);
+        }
+        #[cfg(feature = "tracing")]
+        crate::trace::record_response(&response, elapsed);
+        response
+    }
+
+    /// Set a default timeout for whole requests, from connecting until the response body has been read.
+    /// Individual requests may override it.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// The default timeout for whole requests, if any.
+    pub fn get_timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    /// Set a timeout for establishing connections.
+    /// This only configures the default ReqwestTransport, and is ignored when a custom transport is set.
+    pub fn connect_timeout(mut self, connect_timeout: Duration) -> Self {
+        if self.custom_transport {
+            return self;
+        }
+        // Building only fails when the TLS backend cannot be initialised, as would `reqwest::Client::new`.
+        let client = reqwest::Client::builder()
+            .connect_timeout(connect_timeout)
+            .build()
+            .expect("failed to build the HTTP client");
+        self.transport = Arc::new(ReqwestTransport::new(client));
+        self
+    }
+
+    /// Limit the number of requests this client has in flight at once.
+    pub fn max_concurrency(mut self, max_concurrency: usize) -> Self {
+        if max_concurrency > 0 {
+            self.concurrency = Some(Semaphore::new(max_concurrency));
+        }
+        self
+    }
+
+    /// Limit the rate at which this client sends requests, spreading `requests` evenly over every `period`.
+    pub fn rate_limit(mut self, requests: u32, period: Duration) -> Self {
+        if requests > 0 {
+            self.min_interval = Some(period / requests);
+        }
+        self
+    }
+
+    /// A snapshot of the requests made by this client so far.
+    pub fn usage_stats(&self) -> UsageStats {
+        self.usage.lock().unwrap().clone()
+    }
+
+    /// Clear the usage statistics, e.g. at the start of a new quota period.
+    pub fn reset_usage_stats(&self) {
+        *self.usage.lock().unwrap() = UsageStats::default();
+    }
+
+    pub(crate) fn record_usage(
+        &self,
+        endpoint: &'static str,
+        country: Option<&'static str>,
+        latency: Duration,
+        bytes_received: usize,
+        error: Option<ErrorKind>,
+    ) {
+        self.usage
+            .lock()
+            .unwrap()
+            .record(endpoint, country, latency, bytes_received, error);
+    }
+
+    /// Waits until the concurrency and rate limits allow another request to be sent.
+    /// The returned permit must be held for as long as the request is in flight.
+    pub(crate) async fn throttle(&self) -> Option<SemaphorePermit<'_>> {
+        let permit = match &self.concurrency {
+            Some(semaphore) => semaphore.acquire().await.ok(),
+            None => None,
+        };
+        self.wait_for_slot().await;
+        permit
+    }
+
+    /// Waits until the rate limit allows another request to be sent.
+    pub(crate) async fn wait_for_slot(&self) {
+        if let Some(interval) = self.min_interval {
+            let wait = {
+                let mut next_slot = self.next_slot.lock().unwrap();
+                let now = Instant::now();
+                let slot = next_slot.map_or(now, |next| next.max(now));
+                *next_slot = Some(slot + interval);
+                slot - now
+            };
+            if !wait.is_zero() {
+                tokio::time::sleep(wait).await;
+            }
         }
     }
 
     /// Return the current version of this API
-    pub fn api_version(&self) -> VersionRequest {
+    pub fn api_version(&self) -> VersionRequest<'_> {
         VersionRequest::new(self)
     }
 
     /// List available job categories
-    pub fn categories(&self) -> CategoriesRequest {
+    pub fn categories(&self) -> CategoriesRequest<'_> {
         CategoriesRequest::new(self)
     }
 
     /// Provides historical average salary data
-    pub fn history(&self) -> HistoryRequest {
+    pub fn history(&self) -> HistoryRequest<'_> {
         HistoryRequest::new(self)
     }
 
     /// Provides salary data for locations inside an area
-    pub fn geodata(&self) -> GeodataRequest {
+    pub fn geodata(&self) -> GeodataRequest<'_> {
         GeodataRequest::new(self)
     }
 
     /// List the top employers for the search terms supplied
-    pub fn top_companies(&self) -> TopCompaniesRequest {
+    pub fn top_companies(&self) -> TopCompaniesRequest<'_> {
         TopCompaniesRequest::new(self)
     }
 
     /// Provide histogram data of salary data
-    pub fn histogram(&self) -> HistogramRequest {
+    pub fn histogram(&self) -> HistogramRequest<'_> {
         HistogramRequest::new(self)
     }
 
     /// Search the Adzuna job database
-    pub fn search(&self) -> SearchRequest {
+    pub fn search(&self) -> SearchRequest<'_> {
         SearchRequest::new(self)
     }
+
+    /// Crawl the geodata endpoint into a tree of locations with job counts
+    pub fn location_tree(&self) -> LocationTreeCrawler<'_> {
+        LocationTreeCrawler::new(self)
+    }
+
+    /// Execute many requests together with bounded concurrency
+    pub fn batch<'a, T: Send + 'a>(&'a self) -> Batch<'a, T> {
+        Batch::new()
+    }
+
+    /// Compare salary statistics across categories and locations
+    pub fn salary_report(&self) -> SalaryReportBuilder<'_> {
+        SalaryReportBuilder::new(self)
+    }
+
+    /// Gather a profile of a company, by its canonical name
+    pub fn company_profile(&self, canonical_name: &str) -> CompanyProfileBuilder<'_> {
+        CompanyProfileBuilder::new(self, canonical_name)
+    }
 }
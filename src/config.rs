@@ -0,0 +1,213 @@
+use crate::client::Client;
+use crate::credentials::{CredentialPool, Credentials};
+use crate::models::{Country, Location, Parameters, SortBy, SortDirection};
+use serde::Deserialize;
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+use std::time::Duration;
+
+#[derive(Debug)]
+pub enum ConfigError {
+    /// The configuration file could not be read.
+    Io(std::io::Error),
+    /// The configuration could not be parsed.
+    Parse(String),
+    /// A required setting was not provided.
+    Missing(&'static str),
+}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigError::Io(error) => {
+                write!(f, "failed to read configuration: {error}")
+            }
+            ConfigError::Parse(error) => {
+                write!(f, "failed to parse configuration: {error}")
+            }
+            ConfigError::Missing(setting) => {
+                write!(f, "missing configuration setting `{setting}`")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+/// A limit of `requests` spread evenly over every `period_secs` seconds.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct RateLimitConfig {
+    pub requests: u32,
+    pub period_secs: f64,
+}
+
+/// Values applied to every new request builder of a Client.
+/// Each of them can still be overridden on the builder.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Defaults {
+    /// The country requests are sent for. Defaults to US.
+    pub country: Option<Country>,
+    /// The category tag to filter with.
+    pub category: Option<String>,
+    /// The location to filter with. Locations added with `location` refine it, while `location_path` replaces it.
+    pub location: Option<Location>,
+    /// The number of results on a page of search results. Zero is ignored.
+    pub results_per_page: Option<usize>,
+    /// The ordering of search results.
+    pub sort_by: Option<SortBy>,
+    /// The direction search results are ordered in.
+    pub sort_dir: Option<SortDirection>,
+}
+
+impl Defaults {
+    /// Apply the defaults shared by every endpoint which filters by category and location.
+    pub(crate) fn apply_filters(&self, parameters: &mut Parameters) {
+        if let Some(category) = &self.category {
+            parameters.category = Some(category.clone());
+        }
+        if let Some(location) = &self.location {
+            parameters.locations = location.area().to_vec();
+        }
+    }
+
+    /// Apply the defaults for the search endpoint.
+    pub(crate) fn apply_search(&self, parameters: &mut Parameters) {
+        self.apply_filters(parameters);
+        if let Some(results_per_page) = self.results_per_page.filter(|&n| n > 0) {
+            parameters.results_per_page = Some(results_per_page);
+        }
+        if let Some(sort_by) = self.sort_by {
+            parameters.sort_by = Some(sort_by.to_string());
+        }
+        if let Some(sort_dir) = self.sort_dir {
+            parameters.sort_dir = Some(sort_dir.to_string());
+        }
+    }
+}
+
+/// Settings for building a Client, usually loaded from a TOML or JSON file.
+///
+/// ```toml
+/// timeout_secs = 10
+///
+/// [defaults]
+/// country = "gb"
+/// results_per_page = 50
+/// sort_by = "date"
+///
+/// [[credentials]]
+/// app_id = "123"
+/// app_key = "abc"
+///
+/// [rate_limit]
+/// requests = 25
+/// period_secs = 60
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ClientConfig {
+    /// The sets of credentials to rotate through. At least one is required.
+    pub credentials: Vec<Credentials>,
+    /// Overrides the root URL of the API.
+    pub base_url: Option<String>,
+    /// Values applied to every new request builder.
+    pub defaults: Defaults,
+    /// The default timeout for whole requests, in seconds.
+    pub timeout_secs: Option<f64>,
+    /// The timeout for establishing connections, in seconds.
+    pub connect_timeout_secs: Option<f64>,
+    /// The maximum number of requests in flight at once.
+    pub max_concurrency: Option<usize>,
+    pub rate_limit: Option<RateLimitConfig>,
+}
+
+impl ClientConfig {
+    /// Parse a configuration in TOML format.
+    pub fn from_toml(config: &str) -> Result<Self, ConfigError> {
+        toml::from_str(config).map_err(|e| ConfigError::Parse(e.to_string()))
+    }
+
+    /// Parse a configuration in JSON format.
+    pub fn from_json(config: &str) -> Result<Self, ConfigError> {
+        serde_json::from_str(config).map_err(|e| ConfigError::Parse(e.to_string()))
+    }
+
+    /// Read a configuration file, in JSON format if its extension is `.json` and TOML otherwise.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, ConfigError> {
+        let path = path.as_ref();
+        let config = std::fs::read_to_string(path).map_err(ConfigError::Io)?;
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some(extension) if extension.eq_ignore_ascii_case("json") => Self::from_json(&config),
+            _ => Self::from_toml(&config),
+        }
+    }
+
+    /// Read the configuration from environment variables:
+    ///  - `ADZUNA_APP_ID` and `ADZUNA_APP_KEY`, or `API_ID` and `API_KEY`, for the credentials.
+    ///  - `ADZUNA_COUNTRY` for the default country code in `defaults`, optionally.
+    ///  - `ADZUNA_BASE_URL` for the root URL of the API, optionally.
+    ///
+    /// No other setting is read from the environment.
+    pub fn from_env() -> Result<Self, ConfigError> {
+        let app_id = env_var("ADZUNA_APP_ID")
+            .or_else(|| env_var("API_ID"))
+            .ok_or(ConfigError::Missing("ADZUNA_APP_ID"))?;
+        let app_key = env_var("ADZUNA_APP_KEY")
+            .or_else(|| env_var("API_KEY"))
+            .ok_or(ConfigError::Missing("ADZUNA_APP_KEY"))?;
+        let country = match env_var("ADZUNA_COUNTRY") {
+            Some(code) => Some(Country::from_code(&code).ok_or_else(|| {
+                ConfigError::Parse(format!("unknown country code `{code}` in ADZUNA_COUNTRY"))
+            })?),
+            None => None,
+        };
+
+        Ok(Self {
+            credentials: vec![Credentials::new(app_id, app_key)],
+            base_url: env_var("ADZUNA_BASE_URL"),
+            defaults: Defaults {
+                country,
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+    }
+
+    /// Build a client with these settings.
+    pub fn build(&self) -> Result<Client, ConfigError> {
+        if self.credentials.is_empty() {
+            return Err(ConfigError::Missing("credentials"));
+        }
+        let mut client = Client::with_credentials(CredentialPool::new(self.credentials.clone()));
+        if let Some(base_url) = &self.base_url {
+            client = client.base_url(base_url);
+        }
+        client = client.defaults(self.defaults.clone());
+        if let Some(timeout) = self.timeout_secs {
+            client = client.timeout(seconds(timeout, "timeout_secs")?);
+        }
+        if let Some(connect_timeout) = self.connect_timeout_secs {
+            client = client.connect_timeout(seconds(connect_timeout, "connect_timeout_secs")?);
+        }
+        if let Some(max_concurrency) = self.max_concurrency {
+            client = client.max_concurrency(max_concurrency);
+        }
+        if let Some(rate_limit) = &self.rate_limit {
+            client = client.rate_limit(
+                rate_limit.requests,
+                seconds(rate_limit.period_secs, "rate_limit.period_secs")?,
+            );
+        }
+        Ok(client)
+    }
+}
+
+fn env_var(name: &str) -> Option<String> {
+    std::env::var(name).ok().filter(|value| !value.is_empty())
+}
+
+fn seconds(seconds: f64, setting: &str) -> Result<Duration, ConfigError> {
+    Duration::try_from_secs_f64(seconds)
+        .map_err(|_| ConfigError::Parse(format!("`{setting}` must be a positive number")))
+}
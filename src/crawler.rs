@@ -0,0 +1,214 @@
+use crate::client::Client;
+use crate::models::{self, Location};
+use crate::request::{Error, RequestBuilder};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// A location discovered while crawling the geodata endpoint.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LocationNode {
+    /// The full location hierarchy, which can be passed back to `location_path`.
+    pub location: Location,
+    /// A human readable name for the location, if one was returned.
+    pub display_name: Option<String>,
+    /// The number of jobs available at this location.
+    /// For the root of a tree this is the sum over its children.
+    pub count: Option<usize>,
+    /// The locations one level below this one, as far as they were crawled.
+    pub children: Vec<LocationNode>,
+}
+
+impl LocationNode {
+    fn new(location: Location) -> Self {
+        Self {
+            location,
+            display_name: None,
+            count: None,
+            children: Vec::new(),
+        }
+    }
+
+    /// Find a node by its location within this subtree.
+    pub fn find(&self, location: &Location) -> Option<&LocationNode> {
+        if &self.location == location {
+            return Some(self);
+        }
+        self.children
+            .iter()
+            .find(|child| child.location == *location || child.location.contains(location))
+            .and_then(|child| child.find(location))
+    }
+
+    /// All the nodes of this subtree, in depth-first order.
+    pub fn nodes(&self) -> Vec<&LocationNode> {
+        let mut nodes = vec![self];
+        for child in &self.children {
+            nodes.extend(child.nodes());
+        }
+        nodes
+    }
+
+    fn find_mut(&mut self, location: &Location) -> Option<&mut LocationNode> {
+        if &self.location == location {
+            return Some(self);
+        }
+        self.children
+            .iter_mut()
+            .find(|child| child.location == *location || child.location.contains(location))
+            .and_then(|child| child.find_mut(location))
+    }
+}
+
+/// The result of a crawl, rooted at the location the crawl started from.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LocationTree {
+    /// The country code the tree was crawled for.
+    pub country: String,
+    /// The starting location and everything discovered below it.
+    pub root: LocationNode,
+    /// The number of geodata requests made.
+    pub requests: usize,
+    /// Whether every location within the depth limit was crawled before the request budget ran out.
+    pub complete: bool,
+}
+
+/// Builds a tree of locations by recursively querying the geodata endpoint.
+///
+/// Locations are visited breadth-first, so a limited request budget is spent on the upper levels first.
+pub struct LocationTreeCrawler<'a> {
+    client: &'a Client,
+    country: models::Country,
+    start: Option<Location>,
+    category: Option<String>,
+    max_depth: usize,
+    max_requests: usize,
+}
+
+impl<'a> LocationTreeCrawler<'a> {
+    pub(crate) fn new(client: &'a Client) -> Self {
+        Self {
+            client,
+            country: client.get_default_country(),
+            start: client.get_defaults().location.clone(),
+            category: client.get_defaults().category.clone(),
+            max_depth: 2,
+            max_requests: 50,
+        }
+    }
+
+    /// Crawl a country of interest. Defaults to the client's default country.
+    pub fn country(mut self, country: models::Country) -> Self {
+        self.country = country;
+        self
+    }
+
+    /// Start from a location below the country instead of the whole country.
+    /// Defaults to the client's default location, if any.
+    pub fn start(mut self, location: &Location) -> Self {
+        self.start = Some(location.clone());
+        self
+    }
+
+    /// Only count jobs with a category tag, as returned by the "category" endpoint.
+    /// Defaults to the client's default category, if any.
+    pub fn category(mut self, category: &str) -> Self {
+        self.category = Some(category.into());
+        self
+    }
+
+    /// Set how many levels below the starting location to crawl, at least 1. Defaults to 2.
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth.max(1);
+        self
+    }
+
+    /// Set the maximum number of requests the crawl may make. Defaults to 50.
+    pub fn max_requests(mut self, max_requests: usize) -> Self {
+        self.max_requests = max_requests;
+        self
+    }
+
+    /// Executes the crawl, stopping at the first failed request.
+    pub async fn crawl(&self) -> Result<LocationTree, Error> {
+        let mut root: Option<LocationNode> = self.start.clone().map(LocationNode::new);
+        let mut queue = VecDeque::from([(self.start.clone(), 0)]);
+        let mut requests = 0;
+
+        while requests < self.max_requests {
+            let (parent, depth) = match queue.pop_front() {
+                Some(next) => next,
+                None => break,
+            };
+
+            let mut request = self.client.geodata().country(self.country);
+            if let Some(parent) = &parent {
+                request = request.location_path(parent);
+            }
+            if let Some(category) = &self.category {
+                request = request.category(category);
+            }
+            let geodata = request.fetch().await?;
+            requests += 1;
+
+            for jobs in geodata.locations.unwrap_or_default() {
+                let detail = match jobs.location {
+                    Some(detail) => detail,
+                    None => continue,
+                };
+                let location = match Location::try_from(&detail) {
+                    Ok(location) => location,
+                    Err(_) => continue,
+                };
+                let parent_location = match &parent {
+                    Some(parent) if parent.contains(&location) => parent.clone(),
+                    Some(_) => continue,
+                    // Crawling a whole country: the root is named after the country in the results.
+                    None => Location::new(location.country()),
+                };
+                if parent_location.depth() + 1 != location.depth() {
+                    continue;
+                }
+
+                let root = root.get_or_insert_with(|| LocationNode::new(parent_location.clone()));
+                let parent_node = match root.find_mut(&parent_location) {
+                    Some(node) => node,
+                    None => continue,
+                };
+                if parent_node.children.iter().any(|c| c.location == location) {
+                    continue;
+                }
+                if depth + 1 < self.max_depth && location.depth() + 1 < models::MAX_LOCATION_LEVELS
+                {
+                    queue.push_back((Some(location.clone()), depth + 1));
+                }
+                parent_node.children.push(LocationNode {
+                    location,
+                    display_name: detail.display_name,
+                    count: jobs.count,
+                    children: Vec::new(),
+                });
+            }
+        }
+
+        let mut root =
+            root.unwrap_or_else(|| LocationNode::new(Location::new(&root_area(self.country))));
+        if root.count.is_none() {
+            root.count = Some(root.children.iter().filter_map(|c| c.count).sum());
+        }
+
+        Ok(LocationTree {
+            country: self.country.to_code().into(),
+            root,
+            requests,
+            complete: queue.is_empty(),
+        })
+    }
+}
+
+/// The name the API gives to the top level area of a country, used when no location was returned to take it from.
+fn root_area(country: models::Country) -> String {
+    match country {
+        models::Country::UnitedKingdom => "UK".into(),
+        country => country.to_code().to_uppercase(),
+    }
+}
@@ -0,0 +1,213 @@
+use reqwest::StatusCode;
+use serde::Deserialize;
+use std::fmt::{Debug, Display, Formatter};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// A confidential string, which is redacted when printed.
+#[derive(Clone, PartialEq, Eq, Deserialize)]
+#[serde(transparent)]
+pub struct Secret(String);
+
+impl Secret {
+    pub fn new(secret: String) -> Self {
+        Self(secret)
+    }
+
+    /// The actual value of the secret.
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Debug for Secret {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Secret([redacted])")
+    }
+}
+
+impl Display for Secret {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[redacted]")
+    }
+}
+
+impl From<String> for Secret {
+    fn from(secret: String) -> Self {
+        Self(secret)
+    }
+}
+
+/// An app_id and app_key pair, as obtained by registering for the API.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Credentials {
+    pub app_id: String,
+    pub app_key: Secret,
+}
+
+impl Credentials {
+    pub fn new(app_id: String, app_key: String) -> Self {
+        Self {
+            app_id,
+            app_key: Secret::new(app_key),
+        }
+    }
+}
+
+/// How a CredentialPool picks the credentials for the next request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rotation {
+    /// Use each set of credentials in turn.
+    RoundRobin,
+    /// Keep using the same credentials until they are rate limited or rejected.
+    OnFailure,
+}
+
+/// Usage of one set of credentials within a CredentialPool.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CredentialUsage {
+    pub app_id: String,
+    /// The number of requests sent with these credentials.
+    pub requests: u64,
+    /// The number of requests rejected for exceeding the rate limit.
+    pub rate_limited: u64,
+    /// The number of requests rejected as unauthorised.
+    pub auth_failures: u64,
+    /// Whether the credentials are currently set aside.
+    pub disabled: bool,
+}
+
+#[derive(Debug, Default)]
+struct KeyState {
+    requests: u64,
+    rate_limited: u64,
+    auth_failures: u64,
+    disabled_until: Option<Instant>,
+}
+
+#[derive(Debug)]
+struct PoolState {
+    cursor: usize,
+    keys: Vec<KeyState>,
+}
+
+/// Several sets of credentials shared by a Client, each with its own quota.
+///
+/// Credentials which are rate limited or rejected are set aside for a cooldown period,
+/// and the request is retried with the next available credentials.
+#[derive(Debug)]
+pub struct CredentialPool {
+    credentials: Vec<Credentials>,
+    rotation: Rotation,
+    cooldown: Duration,
+    state: Mutex<PoolState>,
+}
+
+impl CredentialPool {
+    /// Create a pool rotating round-robin through the given credentials. The list must not be empty.
+    pub fn new(credentials: Vec<Credentials>) -> Self {
+        assert!(
+            !credentials.is_empty(),
+            "a credential pool needs at least one set of credentials"
+        );
+        let keys = credentials.iter().map(|_| KeyState::default()).collect();
+        Self {
+            credentials,
+            rotation: Rotation::RoundRobin,
+            cooldown: Duration::from_secs(60),
+            state: Mutex::new(PoolState { cursor: 0, keys }),
+        }
+    }
+
+    /// Create a pool with a single set of credentials.
+    pub fn single(app_id: String, app_key: String) -> Self {
+        Self::new(vec![Credentials::new(app_id, app_key)])
+    }
+
+    /// Set how the next credentials are picked. Defaults to round-robin.
+    pub fn rotation(mut self, rotation: Rotation) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    /// Set how long failing credentials are set aside for. Defaults to one minute.
+    pub fn cooldown(mut self, cooldown: Duration) -> Self {
+        self.cooldown = cooldown;
+        self
+    }
+
+    /// The number of credentials in the pool.
+    pub fn len(&self) -> usize {
+        self.credentials.len()
+    }
+
+    /// Whether the pool is empty, which is never the case.
+    pub fn is_empty(&self) -> bool {
+        self.credentials.is_empty()
+    }
+
+    /// The usage of every set of credentials, in the order they were given.
+    pub fn usage(&self) -> Vec<CredentialUsage> {
+        let state = self.state.lock().unwrap();
+        let now = Instant::now();
+        self.credentials
+            .iter()
+            .zip(&state.keys)
+            .map(|(credentials, key)| CredentialUsage {
+                app_id: credentials.app_id.clone(),
+                requests: key.requests,
+                rate_limited: key.rate_limited,
+                auth_failures: key.auth_failures,
+                disabled: key.disabled_until.is_some_and(|until| until > now),
+            })
+            .collect()
+    }
+
+    /// Pick the credentials for the next request.
+    /// When every set is disabled, the one which becomes available first is used anyway.
+    pub(crate) fn acquire(&self) -> (usize, &Credentials) {
+        let mut state = self.state.lock().unwrap();
+        let now = Instant::now();
+        let len = self.credentials.len();
+        let start = state.cursor;
+        let index = (0..len)
+            .map(|offset| (start + offset) % len)
+            .find(|&i| {
+                state.keys[i]
+                    .disabled_until
+                    .is_none_or(|until| until <= now)
+            })
+            .unwrap_or_else(|| {
+                (0..len)
+                    .min_by_key(|&i| state.keys[i].disabled_until)
+                    .unwrap_or(start)
+            });
+
+        state.keys[index].requests += 1;
+        state.cursor = match self.rotation {
+            Rotation::RoundRobin => (index + 1) % len,
+            Rotation::OnFailure => index,
+        };
+        (index, &self.credentials[index])
+    }
+
+    /// Record the status a request got with the credentials at `index`.
+    /// Returns whether the request should be retried with other credentials.
+    pub(crate) fn report(&self, index: usize, status: StatusCode) -> bool {
+        let mut state = self.state.lock().unwrap();
+        let key = &mut state.keys[index];
+        match status {
+            StatusCode::TOO_MANY_REQUESTS => key.rate_limited += 1,
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => key.auth_failures += 1,
+            _ => {
+                key.disabled_until = None;
+                return false;
+            }
+        }
+        key.disabled_until = Some(Instant::now() + self.cooldown);
+        if state.cursor == index {
+            state.cursor = (index + 1) % self.credentials.len();
+        }
+        self.credentials.len() > 1
+    }
+}
@@ -0,0 +1,178 @@
+use crate::models::Job;
+use std::collections::{HashMap, HashSet};
+
+/// Why a job was considered a duplicate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DuplicateReason {
+    /// The same advertisement was returned again, with the same `id`.
+    SameId,
+    /// A different advertisement with the same title, company, location and salary, typically posted by an aggregator.
+    SimilarListing,
+}
+
+/// A job dropped by a Deduplicator, and the job it was merged into.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Duplicate {
+    /// The `id` of the job which was kept.
+    pub kept_id: String,
+    /// The `id` of the job which was dropped.
+    pub duplicate_id: String,
+    pub reason: DuplicateReason,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct ListingKey {
+    title: String,
+    company: String,
+    location: String,
+    salary: (i64, i64),
+}
+
+/// Removes jobs already seen, across pages and queries.
+///
+/// The first occurrence of a job is kept. Later jobs are dropped when they have the same `id`,
+/// or when their normalized title, company and location match and their salaries round to the same values.
+/// Listings with neither a company nor a salary are only matched by `id`, as their titles and locations alone
+/// are too common to tell them apart.
+///
+/// `adref` is not compared, as the API does not document it as identifying the advertisement.
+#[derive(Debug, Clone)]
+pub struct Deduplicator {
+    ids: HashSet<String>,
+    listings: HashMap<ListingKey, String>,
+    salary_precision: f64,
+    duplicates: Vec<Duplicate>,
+}
+
+impl Default for Deduplicator {
+    fn default() -> Self {
+        Self {
+            ids: HashSet::new(),
+            listings: HashMap::new(),
+            salary_precision: 1000.0,
+            duplicates: Vec::new(),
+        }
+    }
+}
+
+impl Deduplicator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the amount salaries are rounded to before comparing listings. Defaults to 1000.
+    pub fn salary_precision(mut self, salary_precision: f64) -> Self {
+        if salary_precision > 0.0 {
+            self.salary_precision = salary_precision;
+        }
+        self
+    }
+
+    /// Record a job, returning the duplicate it makes if it was already seen.
+    pub fn insert(&mut self, job: &Job) -> Option<Duplicate> {
+        let key = self.listing_key(job);
+
+        let duplicate = if self.ids.contains(&job.id) {
+            Some(Duplicate {
+                kept_id: job.id.clone(),
+                duplicate_id: job.id.clone(),
+                reason: DuplicateReason::SameId,
+            })
+        } else {
+            key.as_ref()
+                .and_then(|key| self.listings.get(key))
+                .map(|kept_id| Duplicate {
+                    kept_id: kept_id.clone(),
+                    duplicate_id: job.id.clone(),
+                    reason: DuplicateReason::SimilarListing,
+                })
+        };
+
+        match &duplicate {
+            Some(duplicate) => self.duplicates.push(duplicate.clone()),
+            None => {
+                self.ids.insert(job.id.clone());
+                if let Some(key) = key {
+                    self.listings.insert(key, job.id.clone());
+                }
+            }
+        }
+        duplicate
+    }
+
+    /// Keep only the jobs which were not seen before, in their original order.
+    pub fn deduplicate(&mut self, jobs: Vec<Job>) -> Vec<Job> {
+        jobs.into_iter()
+            .filter(|job| self.insert(job).is_none())
+            .collect()
+    }
+
+    /// Every duplicate found so far, in the order they were found.
+    pub fn duplicates(&self) -> &[Duplicate] {
+        &self.duplicates
+    }
+
+    /// The number of distinct jobs seen so far.
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    /// Whether no job has been seen yet.
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    fn listing_key(&self, job: &Job) -> Option<ListingKey> {
+        let company = normalize(
+            job.company
+                .canonical_name
+                .as_deref()
+                .or(job.company.display_name.as_deref())
+                .unwrap_or_default(),
+        );
+        if company.is_empty() && job.salary_min <= 0.0 && job.salary_max <= 0.0 {
+            return None;
+        }
+        let location = match &job.location.area {
+            Some(area) => area.join(" "),
+            None => job.location.display_name.clone().unwrap_or_default(),
+        };
+        let round = |salary: f64| (salary / self.salary_precision).round() as i64;
+
+        Some(ListingKey {
+            title: normalize_title(&job.title),
+            company,
+            location: normalize(&location),
+            salary: (round(job.salary_min), round(job.salary_max)),
+        })
+    }
+}
+
+/// Lowercase, with punctuation removed and whitespace collapsed.
+fn normalize(text: &str) -> String {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Titles are also stripped of bracketed asides, which aggregators use for details such as `(Full Time)`.
+fn normalize_title(title: &str) -> String {
+    let mut depth = 0usize;
+    let stripped: String = title
+        .chars()
+        .filter(|&c| {
+            match c {
+                '(' | '[' | '{' => depth += 1,
+                ')' | ']' | '}' => {
+                    depth = depth.saturating_sub(1);
+                    return false;
+                }
+                _ => {}
+            }
+            depth == 0
+        })
+        .collect();
+    normalize(&stripped)
+}
@@ -0,0 +1,152 @@
+#[cfg(feature = "arrow")]
+pub mod arrow;
+pub mod csv;
+pub mod geojson;
+
+use crate::models::{ContractTime, ContractType, Job};
+use serde_json::{json, Value};
+use std::borrow::Cow;
+
+/// A field of a job that can be selected for export.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum JobField {
+    Id,
+    Created,
+    Title,
+    Description,
+    RedirectUrl,
+    Latitude,
+    Longitude,
+    CategoryTag,
+    CategoryLabel,
+    /// The display name of the location.
+    Location,
+    /// The location hierarchy, joined with " > ".
+    LocationArea,
+    /// One level of the location hierarchy, where 0 is the country.
+    Area(usize),
+    SalaryMin,
+    SalaryMax,
+    SalaryIsPredicted,
+    Company,
+    CompanyCanonicalName,
+    ContractType,
+    ContractTime,
+    Adref,
+}
+
+impl JobField {
+    /// Every field, in the order of the `Job` struct.
+    pub const ALL: [JobField; 19] = [
+        JobField::Id,
+        JobField::Created,
+        JobField::Title,
+        JobField::Description,
+        JobField::RedirectUrl,
+        JobField::Latitude,
+        JobField::Longitude,
+        JobField::CategoryTag,
+        JobField::CategoryLabel,
+        JobField::Location,
+        JobField::LocationArea,
+        JobField::SalaryMin,
+        JobField::SalaryMax,
+        JobField::SalaryIsPredicted,
+        JobField::Company,
+        JobField::CompanyCanonicalName,
+        JobField::ContractType,
+        JobField::ContractTime,
+        JobField::Adref,
+    ];
+
+    /// The fields exported when none are selected.
+    pub const DEFAULT: [JobField; 9] = [
+        JobField::Id,
+        JobField::Created,
+        JobField::Title,
+        JobField::Company,
+        JobField::Location,
+        JobField::CategoryTag,
+        JobField::SalaryMin,
+        JobField::SalaryMax,
+        JobField::RedirectUrl,
+    ];
+
+    /// Every field, with the location hierarchy flattened to its first `levels` levels.
+    pub fn flattened(levels: usize) -> Vec<JobField> {
+        let mut fields = Vec::with_capacity(Self::ALL.len() + levels);
+        for field in Self::ALL {
+            match field {
+                JobField::LocationArea => fields.extend((0..levels).map(JobField::Area)),
+                field => fields.push(field),
+            }
+        }
+        fields
+    }
+
+    /// The name of the field, used as a property name or column header.
+    pub fn name(&self) -> Cow<'static, str> {
+        Cow::Borrowed(match self {
+            JobField::Id => "id",
+            JobField::Created => "created",
+            JobField::Title => "title",
+            JobField::Description => "description",
+            JobField::RedirectUrl => "redirect_url",
+            JobField::Latitude => "latitude",
+            JobField::Longitude => "longitude",
+            JobField::CategoryTag => "category",
+            JobField::CategoryLabel => "category_label",
+            JobField::Location => "location",
+            JobField::LocationArea => "location_area",
+            JobField::SalaryMin => "salary_min",
+            JobField::SalaryMax => "salary_max",
+            JobField::SalaryIsPredicted => "salary_is_predicted",
+            JobField::Company => "company",
+            JobField::CompanyCanonicalName => "company_canonical_name",
+            JobField::ContractType => "contract_type",
+            JobField::ContractTime => "contract_time",
+            JobField::Area(level) => return Cow::Owned(format!("area_{level}")),
+            JobField::Adref => "adref",
+        })
+    }
+
+    /// The value of the field for a job, `null` when it is missing.
+    pub fn value(&self, job: &Job) -> Value {
+        match self {
+            JobField::Id => json!(job.id),
+            JobField::Created => json!(job.created),
+            JobField::Title => json!(job.title),
+            JobField::Description => json!(job.description),
+            JobField::RedirectUrl => json!(job.redirect_url),
+            JobField::Latitude => json!(job.latitude),
+            JobField::Longitude => json!(job.longitude),
+            JobField::CategoryTag => json!(job.category.tag),
+            JobField::CategoryLabel => json!(job.category.label),
+            JobField::Location => json!(job.location.display_name),
+            JobField::LocationArea => {
+                json!(job.location.area.as_ref().map(|area| area.join(" > ")))
+            }
+            JobField::Area(level) => {
+                json!(job.location.area.as_ref().and_then(|area| area.get(*level)))
+            }
+            JobField::SalaryMin => json!(job.salary_min),
+            JobField::SalaryMax => json!(job.salary_max),
+            JobField::SalaryIsPredicted => json!(job.salary_is_predicted),
+            JobField::Company => json!(job.company.display_name),
+            JobField::CompanyCanonicalName => json!(job.company.canonical_name),
+            JobField::ContractType => {
+                json!(job.contract_type.map(|contract_type| match contract_type {
+                    ContractType::Permanent => "permanent",
+                    ContractType::Contract => "contract",
+                }))
+            }
+            JobField::ContractTime => {
+                json!(job.contract_time.map(|contract_time| match contract_time {
+                    ContractTime::FullTime => "full_time",
+                    ContractTime::PartTime => "part_time",
+                }))
+            }
+            JobField::Adref => json!(job.adref),
+        }
+    }
+}
@@ -0,0 +1,240 @@
+use crate::analytics::histogram::SalaryDistribution;
+use crate::analytics::history::SalaryTimeSeries;
+use crate::analytics::leaderboard::Leaderboard;
+use crate::export::JobField;
+use crate::models::{HistoricalSalary, Job, SalaryHistogram, TopCompanies};
+use arrow_array::builder::{
+    BooleanBuilder, Float64Builder, ListBuilder, StringBuilder, UInt64Builder,
+};
+use arrow_array::{ArrayRef, RecordBatch};
+use arrow_schema::{ArrowError, DataType, Field, Schema, SchemaRef};
+use parquet::arrow::ArrowWriter;
+use parquet::errors::Result as ParquetResult;
+use parquet::format::FileMetaData;
+use std::io::Write;
+use std::sync::{Arc, OnceLock};
+
+/// The schema of job record batches, with a column for every `Job` field.
+///
+/// Columns are only ever added at the end, so files written by older versions stay readable.
+pub fn job_schema() -> SchemaRef {
+    static SCHEMA: OnceLock<SchemaRef> = OnceLock::new();
+    SCHEMA
+        .get_or_init(|| {
+            let text = |name: &str, nullable: bool| Field::new(name, DataType::Utf8, nullable);
+            let number = |name: &str, nullable: bool| Field::new(name, DataType::Float64, nullable);
+            Arc::new(Schema::new(vec![
+                text("id", false),
+                text("created", false),
+                text("title", false),
+                text("description", false),
+                text("redirect_url", false),
+                number("latitude", false),
+                number("longitude", false),
+                text("category_tag", false),
+                text("category_label", false),
+                text("location_display_name", true),
+                Field::new(
+                    "location_area",
+                    DataType::List(Arc::new(Field::new("item", DataType::Utf8, true))),
+                    true,
+                ),
+                number("salary_min", false),
+                number("salary_max", false),
+                Field::new("salary_is_predicted", DataType::Boolean, false),
+                text("company_display_name", true),
+                text("company_canonical_name", true),
+                Field::new("company_count", DataType::UInt64, true),
+                number("company_average_salary", true),
+                text("contract_type", true),
+                text("contract_time", true),
+                text("adref", false),
+            ]))
+        })
+        .clone()
+}
+
+/// Convert jobs to a record batch with the `job_schema`.
+pub fn jobs_to_record_batch<'a>(
+    jobs: impl IntoIterator<Item = &'a Job>,
+) -> Result<RecordBatch, ArrowError> {
+    let mut id = StringBuilder::new();
+    let mut created = StringBuilder::new();
+    let mut title = StringBuilder::new();
+    let mut description = StringBuilder::new();
+    let mut redirect_url = StringBuilder::new();
+    let mut latitude = Float64Builder::new();
+    let mut longitude = Float64Builder::new();
+    let mut category_tag = StringBuilder::new();
+    let mut category_label = StringBuilder::new();
+    let mut location_display_name = StringBuilder::new();
+    let mut location_area = ListBuilder::new(StringBuilder::new());
+    let mut salary_min = Float64Builder::new();
+    let mut salary_max = Float64Builder::new();
+    let mut salary_is_predicted = BooleanBuilder::new();
+    let mut company_display_name = StringBuilder::new();
+    let mut company_canonical_name = StringBuilder::new();
+    let mut company_count = UInt64Builder::new();
+    let mut company_average_salary = Float64Builder::new();
+    let mut contract_type = StringBuilder::new();
+    let mut contract_time = StringBuilder::new();
+    let mut adref = StringBuilder::new();
+
+    for job in jobs {
+        id.append_value(&job.id);
+        created.append_value(&job.created);
+        title.append_value(&job.title);
+        description.append_value(&job.description);
+        redirect_url.append_value(&job.redirect_url);
+        latitude.append_value(job.latitude);
+        longitude.append_value(job.longitude);
+        category_tag.append_value(&job.category.tag);
+        category_label.append_value(&job.category.label);
+        location_display_name.append_option(job.location.display_name.as_ref());
+        match &job.location.area {
+            Some(area) => location_area.append_value(area.iter().map(Some)),
+            None => location_area.append_null(),
+        }
+        salary_min.append_value(job.salary_min);
+        salary_max.append_value(job.salary_max);
+        salary_is_predicted.append_value(job.salary_is_predicted);
+        company_display_name.append_option(job.company.display_name.as_ref());
+        company_canonical_name.append_option(job.company.canonical_name.as_ref());
+        company_count.append_option(job.company.count.map(|count| count as u64));
+        company_average_salary.append_option(job.company.average_salary);
+        contract_type.append_option(JobField::ContractType.value(job).as_str());
+        contract_time.append_option(JobField::ContractTime.value(job).as_str());
+        adref.append_value(&job.adref);
+    }
+
+    let columns: Vec<ArrayRef> = vec![
+        Arc::new(id.finish()),
+        Arc::new(created.finish()),
+        Arc::new(title.finish()),
+        Arc::new(description.finish()),
+        Arc::new(redirect_url.finish()),
+        Arc::new(latitude.finish()),
+        Arc::new(longitude.finish()),
+        Arc::new(category_tag.finish()),
+        Arc::new(category_label.finish()),
+        Arc::new(location_display_name.finish()),
+        Arc::new(location_area.finish()),
+        Arc::new(salary_min.finish()),
+        Arc::new(salary_max.finish()),
+        Arc::new(salary_is_predicted.finish()),
+        Arc::new(company_display_name.finish()),
+        Arc::new(company_canonical_name.finish()),
+        Arc::new(company_count.finish()),
+        Arc::new(company_average_salary.finish()),
+        Arc::new(contract_type.finish()),
+        Arc::new(contract_time.finish()),
+        Arc::new(adref.finish()),
+    ];
+    RecordBatch::try_new(job_schema(), columns)
+}
+
+/// Convert the buckets of a salary histogram to a record batch with `lower`, `upper` and `vacancies` columns.
+pub fn histogram_to_record_batch(histogram: &SalaryHistogram) -> Result<RecordBatch, ArrowError> {
+    let distribution = SalaryDistribution::from_histogram(histogram);
+    let mut lower = Float64Builder::new();
+    let mut upper = Float64Builder::new();
+    let mut vacancies = UInt64Builder::new();
+    for bucket in distribution.buckets() {
+        lower.append_value(bucket.lower);
+        upper.append_option(bucket.upper);
+        vacancies.append_value(bucket.vacancies as u64);
+    }
+    RecordBatch::try_from_iter_with_nullable(vec![
+        ("lower", Arc::new(lower.finish()) as ArrayRef, false),
+        ("upper", Arc::new(upper.finish()) as ArrayRef, true),
+        ("vacancies", Arc::new(vacancies.finish()) as ArrayRef, false),
+    ])
+}
+
+/// Convert a salary history to a record batch with `month` and `salary` columns, in chronological order.
+pub fn history_to_record_batch(history: &HistoricalSalary) -> Result<RecordBatch, ArrowError> {
+    let series = SalaryTimeSeries::from_history(history);
+    let mut month = StringBuilder::new();
+    let mut salary = Float64Builder::new();
+    for point in series.points() {
+        month.append_value(point.month.to_string());
+        salary.append_value(point.salary);
+    }
+    RecordBatch::try_from_iter_with_nullable(vec![
+        ("month", Arc::new(month.finish()) as ArrayRef, false),
+        ("salary", Arc::new(salary.finish()) as ArrayRef, false),
+    ])
+}
+
+/// Convert a "top_companies" leaderboard to a record batch with `rank`, `canonical_name`,
+/// `display_name`, `count` and `average_salary` columns.
+pub fn leaderboard_to_record_batch(
+    top_companies: &TopCompanies,
+) -> Result<RecordBatch, ArrowError> {
+    let mut rank = UInt64Builder::new();
+    let mut canonical_name = StringBuilder::new();
+    let mut display_name = StringBuilder::new();
+    let mut count = UInt64Builder::new();
+    let mut average_salary = Float64Builder::new();
+    for entry in Leaderboard::from(top_companies).entries() {
+        rank.append_value(entry.rank as u64);
+        canonical_name.append_option(entry.company.canonical_name.as_ref());
+        display_name.append_option(entry.company.display_name.as_ref());
+        count.append_option(entry.company.count.map(|count| count as u64));
+        average_salary.append_option(entry.company.average_salary);
+    }
+    RecordBatch::try_from_iter_with_nullable(vec![
+        ("rank", Arc::new(rank.finish()) as ArrayRef, false),
+        (
+            "canonical_name",
+            Arc::new(canonical_name.finish()) as ArrayRef,
+            true,
+        ),
+        (
+            "display_name",
+            Arc::new(display_name.finish()) as ArrayRef,
+            true,
+        ),
+        ("count", Arc::new(count.finish()) as ArrayRef, true),
+        (
+            "average_salary",
+            Arc::new(average_salary.finish()) as ArrayRef,
+            true,
+        ),
+    ])
+}
+
+/// Writes jobs to a Parquet file, a batch at a time, so pages of results can be written as they arrive.
+///
+/// The file is only valid once `close` has been called.
+pub struct JobParquetWriter<W: Write + Send> {
+    writer: ArrowWriter<W>,
+}
+
+impl<W: Write + Send> JobParquetWriter<W> {
+    pub fn new(writer: W) -> ParquetResult<Self> {
+        Ok(Self {
+            writer: ArrowWriter::try_new(writer, job_schema(), None)?,
+        })
+    }
+
+    /// Write a batch of jobs, such as a page of search results.
+    pub fn write<'a>(&mut self, jobs: impl IntoIterator<Item = &'a Job>) -> ParquetResult<()> {
+        self.writer.write(&jobs_to_record_batch(jobs)?)
+    }
+
+    /// Write the Parquet footer and return the file metadata.
+    pub fn close(self) -> ParquetResult<FileMetaData> {
+        self.writer.close()
+    }
+}
+
+/// Write a record batch, such as statistics, as a complete Parquet file.
+pub fn write_parquet(
+    writer: impl Write + Send,
+    batch: &RecordBatch,
+) -> ParquetResult<FileMetaData> {
+    let mut writer = ArrowWriter::try_new(writer, batch.schema(), None)?;
+    writer.write(batch)?;
+    writer.close()
+}
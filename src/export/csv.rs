@@ -0,0 +1,141 @@
+use crate::analytics::histogram::SalaryDistribution;
+use crate::analytics::history::SalaryTimeSeries;
+use crate::analytics::leaderboard::Leaderboard;
+use crate::export::JobField;
+use crate::models::{HistoricalSalary, Job, SalaryHistogram, TopCompanies};
+use serde_json::Value;
+use std::io::Write;
+
+/// The text of a CSV cell, empty for missing values.
+fn cell(value: Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(text) => text,
+        value => value.to_string(),
+    }
+}
+
+/// Writes jobs as CSV rows, one at a time, so pages of results can be written as they arrive.
+///
+/// The header line is written with the first job, or when flushing if there are none.
+pub struct JobCsvWriter<W: Write> {
+    writer: ::csv::Writer<W>,
+    columns: Vec<JobField>,
+    header_written: bool,
+}
+
+impl<W: Write> JobCsvWriter<W> {
+    /// Write to `writer` with every field as a column, and up to five levels of `location.area`.
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer: ::csv::Writer::from_writer(writer),
+            columns: JobField::flattened(5),
+            header_written: false,
+        }
+    }
+
+    /// Select the columns, in order.
+    pub fn columns(mut self, columns: &[JobField]) -> Self {
+        self.columns = columns.to_vec();
+        self
+    }
+
+    fn write_header(&mut self) -> ::csv::Result<()> {
+        if !self.header_written {
+            self.writer
+                .write_record(self.columns.iter().map(|column| column.name().into_owned()))?;
+            self.header_written = true;
+        }
+        Ok(())
+    }
+
+    /// Write a job as a row.
+    pub fn write(&mut self, job: &Job) -> ::csv::Result<()> {
+        self.write_header()?;
+        self.writer
+            .write_record(self.columns.iter().map(|column| cell(column.value(job))))
+    }
+
+    /// Write several jobs as rows.
+    pub fn write_all<'a>(&mut self, jobs: impl IntoIterator<Item = &'a Job>) -> ::csv::Result<()> {
+        for job in jobs {
+            self.write(job)?;
+        }
+        Ok(())
+    }
+
+    /// Flush the rows, writing the header line if no job was written.
+    pub fn flush(&mut self) -> ::csv::Result<()> {
+        self.write_header()?;
+        self.writer.flush()?;
+        Ok(())
+    }
+
+    /// Flush the rows and return the underlying writer.
+    pub fn into_inner(mut self) -> ::csv::Result<W> {
+        self.write_header()?;
+        self.writer
+            .into_inner()
+            .map_err(|error| error.into_error().into())
+    }
+}
+
+/// Write the buckets of a salary histogram, with `lower`, `upper` and `vacancies` columns.
+pub fn write_histogram(writer: impl Write, histogram: &SalaryHistogram) -> ::csv::Result<()> {
+    let mut writer = ::csv::Writer::from_writer(writer);
+    writer.write_record(["lower", "upper", "vacancies"])?;
+    for bucket in SalaryDistribution::from_histogram(histogram).buckets() {
+        writer.write_record([
+            bucket.lower.to_string(),
+            bucket
+                .upper
+                .map(|upper| upper.to_string())
+                .unwrap_or_default(),
+            bucket.vacancies.to_string(),
+        ])?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// Write a salary history in chronological order, with `month` and `salary` columns.
+pub fn write_history(writer: impl Write, history: &HistoricalSalary) -> ::csv::Result<()> {
+    let mut writer = ::csv::Writer::from_writer(writer);
+    writer.write_record(["month", "salary"])?;
+    for point in SalaryTimeSeries::from_history(history).points() {
+        writer.write_record([point.month.to_string(), point.salary.to_string()])?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// Write a "top_companies" leaderboard, with `rank`, `canonical_name`, `display_name`, `count` and
+/// `average_salary` columns.
+pub fn write_leaderboard(writer: impl Write, top_companies: &TopCompanies) -> ::csv::Result<()> {
+    let mut writer = ::csv::Writer::from_writer(writer);
+    writer.write_record([
+        "rank",
+        "canonical_name",
+        "display_name",
+        "count",
+        "average_salary",
+    ])?;
+    for entry in Leaderboard::from(top_companies).entries() {
+        let company = &entry.company;
+        writer.write_record([
+            entry.rank.to_string(),
+            company.canonical_name.clone().unwrap_or_default(),
+            company.display_name.clone().unwrap_or_default(),
+            company
+                .count
+                .map(|count| count.to_string())
+                .unwrap_or_default(),
+            company
+                .average_salary
+                .map(|salary| salary.to_string())
+                .unwrap_or_default(),
+        ])?;
+    }
+    writer.flush()?;
+    Ok(())
+}
@@ -0,0 +1,132 @@
+use crate::crawler::LocationTree;
+use crate::export::JobField;
+use crate::geo::Point;
+use crate::models::{Job, JobGeoData, Location};
+use serde_json::{json, Map, Value};
+use std::io::{self, Write};
+
+/// A GeoJSON point geometry, or `null` without a point.
+fn geometry(point: Option<Point>) -> Value {
+    match point {
+        Some(point) => json!({ "type": "Point", "coordinates": [point.longitude, point.latitude] }),
+        None => Value::Null,
+    }
+}
+
+/// Streams features into a FeatureCollection, without holding the whole collection in memory.
+fn write_collection(
+    mut writer: impl Write,
+    features: impl IntoIterator<Item = (Option<Point>, Map<String, Value>)>,
+) -> io::Result<()> {
+    writer.write_all(br#"{"type":"FeatureCollection","features":["#)?;
+    for (index, (point, properties)) in features.into_iter().enumerate() {
+        if index > 0 {
+            writer.write_all(b",")?;
+        }
+        let feature = json!({
+            "type": "Feature",
+            "geometry": geometry(point),
+            "properties": properties,
+        });
+        serde_json::to_writer(&mut writer, &feature)?;
+    }
+    writer.write_all(b"]}")?;
+    writer.flush()
+}
+
+/// Exports jobs as a GeoJSON FeatureCollection, with a point feature per job.
+///
+/// Jobs without coordinates are kept, with a `null` geometry.
+#[derive(Debug, Clone)]
+pub struct JobFeatures {
+    properties: Vec<JobField>,
+}
+
+impl Default for JobFeatures {
+    fn default() -> Self {
+        Self {
+            properties: JobField::DEFAULT.to_vec(),
+        }
+    }
+}
+
+impl JobFeatures {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Select the fields exported as properties of each feature. Defaults to `JobField::DEFAULT`.
+    pub fn properties(mut self, properties: &[JobField]) -> Self {
+        self.properties = properties.to_vec();
+        self
+    }
+
+    /// Write the jobs to `writer`.
+    pub fn write<'a>(
+        &self,
+        writer: impl Write,
+        jobs: impl IntoIterator<Item = &'a Job>,
+    ) -> io::Result<()> {
+        write_collection(
+            writer,
+            jobs.into_iter().map(|job| {
+                let properties = self
+                    .properties
+                    .iter()
+                    .map(|field| (field.name().to_string(), field.value(job)))
+                    .collect();
+                (Point::of(job), properties)
+            }),
+        )
+    }
+}
+
+/// Write the locations of a "geodata" response as a GeoJSON FeatureCollection, with their job counts.
+///
+/// The API gives no coordinates for locations, so `locate` is asked for them.
+/// Locations it cannot place are kept with a `null` geometry.
+pub fn write_geodata(
+    writer: impl Write,
+    geodata: &JobGeoData,
+    locate: impl Fn(&Location) -> Option<Point>,
+) -> io::Result<()> {
+    let features = geodata.locations.iter().flatten().map(|jobs| {
+        let location = jobs
+            .location
+            .as_ref()
+            .and_then(|detail| Location::try_from(detail).ok());
+        let mut properties = Map::new();
+        properties.insert(
+            "location".into(),
+            json!(location.as_ref().map(|l| l.to_string())),
+        );
+        properties.insert(
+            "display_name".into(),
+            json!(jobs.location.as_ref().and_then(|l| l.display_name.clone())),
+        );
+        properties.insert("count".into(), json!(jobs.count));
+        (location.as_ref().and_then(&locate), properties)
+    });
+    write_collection(writer, features)
+}
+
+/// Write every node of a location tree as a GeoJSON FeatureCollection, in depth-first order.
+///
+/// The API gives no coordinates for locations, so `locate` is asked for them.
+/// Locations it cannot place are kept with a `null` geometry.
+pub fn write_location_tree(
+    writer: impl Write,
+    tree: &LocationTree,
+    locate: impl Fn(&Location) -> Option<Point>,
+) -> io::Result<()> {
+    let features = tree.root.nodes().into_iter().map(|node| {
+        let mut properties = Map::new();
+        properties.insert("location".into(), json!(node.location.to_string()));
+        properties.insert("name".into(), json!(node.location.name()));
+        properties.insert("display_name".into(), json!(node.display_name));
+        properties.insert("depth".into(), json!(node.location.depth()));
+        properties.insert("count".into(), json!(node.count));
+        (locate(&node.location), properties)
+    });
+    write_collection(writer, features)
+}
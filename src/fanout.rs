@@ -0,0 +1,81 @@
+use crate::models::Country;
+use crate::request::{CountryRequest, Error};
+use futures::future::join_all;
+use std::collections::HashMap;
+
+/// The same request sent to several countries at once.
+///
+/// Requests run concurrently, within the concurrency and rate limits of the client.
+pub struct MultiCountryRequest<R> {
+    request: R,
+    countries: Vec<Country>,
+}
+
+/// The outcome of a MultiCountryRequest. A failure in one country does not affect the others.
+#[derive(Debug)]
+pub struct MultiCountryResults<T> {
+    /// Responses from the countries whose request succeeded.
+    pub results: HashMap<Country, T>,
+    /// Errors from the countries whose request failed.
+    pub errors: HashMap<Country, Error>,
+}
+
+impl<T> MultiCountryResults<T> {
+    /// Whether every country returned a response.
+    pub fn is_complete(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+impl<R: CountryRequest> MultiCountryRequest<R> {
+    pub(crate) fn new(request: R, countries: &[Country]) -> Self {
+        let mut unique = Vec::with_capacity(countries.len());
+        for country in countries {
+            if !unique.contains(country) {
+                unique.push(*country);
+            }
+        }
+        Self {
+            request,
+            countries: unique,
+        }
+    }
+
+    /// Add another country to run the request for.
+    pub fn country(mut self, country: Country) -> Self {
+        if !self.countries.contains(&country) {
+            self.countries.push(country);
+        }
+        self
+    }
+
+    /// Builds and executes a request for every country.
+    pub async fn fetch(&self) -> MultiCountryResults<R::Response> {
+        let requests: Vec<R> = self
+            .countries
+            .iter()
+            .map(|country| {
+                let mut request = self.request.clone();
+                request.set_country(*country);
+                request
+            })
+            .collect();
+        let responses = join_all(requests.iter().map(|request| request.fetch())).await;
+
+        let mut results = MultiCountryResults {
+            results: HashMap::new(),
+            errors: HashMap::new(),
+        };
+        for (country, response) in self.countries.iter().zip(responses) {
+            match response {
+                Ok(response) => {
+                    results.results.insert(*country, response);
+                }
+                Err(error) => {
+                    results.errors.insert(*country, error);
+                }
+            }
+        }
+        results
+    }
+}
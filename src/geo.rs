@@ -0,0 +1,192 @@
+use crate::models::Job;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// The mean radius of the Earth, in kilometers.
+pub const EARTH_RADIUS_KM: f64 = 6371.0088;
+
+/// A point on the Earth, in degrees.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Point {
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
+impl Point {
+    pub fn new(latitude: f64, longitude: f64) -> Self {
+        Self {
+            latitude,
+            longitude,
+        }
+    }
+
+    /// The workplace of a job, or `None` when its coordinates are missing or invalid.
+    ///
+    /// Adzuna reports unknown coordinates as `0, 0`, so that point is treated as missing.
+    pub fn of(job: &Job) -> Option<Self> {
+        let point = Self::new(job.latitude, job.longitude);
+        let valid = point.latitude.abs() <= 90.0 && point.longitude.abs() <= 180.0;
+        (valid && (point.latitude, point.longitude) != (0.0, 0.0)).then_some(point)
+    }
+
+    /// The great-circle distance to another point in kilometers, using the haversine formula.
+    pub fn distance_km(&self, other: &Point) -> f64 {
+        let (lat1, lat2) = (self.latitude.to_radians(), other.latitude.to_radians());
+        let d_lat = lat2 - lat1;
+        let d_lon = (other.longitude - self.longitude).to_radians();
+        let a = (d_lat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (d_lon / 2.0).sin().powi(2);
+        2.0 * EARTH_RADIUS_KM * a.sqrt().min(1.0).asin()
+    }
+}
+
+/// An area between two latitudes and two longitudes, in degrees.
+///
+/// A box whose `west` is greater than its `east` crosses the antimeridian.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct BoundingBox {
+    pub south: f64,
+    pub west: f64,
+    pub north: f64,
+    pub east: f64,
+}
+
+impl BoundingBox {
+    pub fn new(south: f64, west: f64, north: f64, east: f64) -> Self {
+        Self {
+            south,
+            west,
+            north,
+            east,
+        }
+    }
+
+    /// The smallest box containing every point, or `None` if there are none.
+    pub fn around(points: impl IntoIterator<Item = Point>) -> Option<Self> {
+        points.into_iter().fold(None, |bounds, point| {
+            let bounds = bounds.unwrap_or(Self::new(
+                point.latitude,
+                point.longitude,
+                point.latitude,
+                point.longitude,
+            ));
+            Some(Self::new(
+                bounds.south.min(point.latitude),
+                bounds.west.min(point.longitude),
+                bounds.north.max(point.latitude),
+                bounds.east.max(point.longitude),
+            ))
+        })
+    }
+
+    pub fn contains(&self, point: &Point) -> bool {
+        let latitude = (self.south..=self.north).contains(&point.latitude);
+        let longitude = if self.west <= self.east {
+            (self.west..=self.east).contains(&point.longitude)
+        } else {
+            point.longitude >= self.west || point.longitude <= self.east
+        };
+        latitude && longitude
+    }
+}
+
+/// A job and its distance from a point.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct JobDistance<'a> {
+    pub job: &'a Job,
+    pub distance_km: f64,
+}
+
+/// Keep the jobs inside a bounding box. Jobs without coordinates are dropped.
+pub fn within_bounds<'a>(
+    jobs: impl IntoIterator<Item = &'a Job>,
+    bounds: &BoundingBox,
+) -> Vec<&'a Job> {
+    jobs.into_iter()
+        .filter(|job| Point::of(job).is_some_and(|point| bounds.contains(&point)))
+        .collect()
+}
+
+/// Keep the jobs within `radius_km` of a point. Jobs without coordinates are dropped.
+pub fn within_radius<'a>(
+    jobs: impl IntoIterator<Item = &'a Job>,
+    center: &Point,
+    radius_km: f64,
+) -> Vec<JobDistance<'a>> {
+    by_distance(jobs, center)
+        .into_iter()
+        .take_while(|job| job.distance_km <= radius_km)
+        .collect()
+}
+
+/// Sort the jobs by distance from a point, nearest first. Jobs without coordinates are dropped.
+pub fn by_distance<'a>(
+    jobs: impl IntoIterator<Item = &'a Job>,
+    center: &Point,
+) -> Vec<JobDistance<'a>> {
+    let mut distances: Vec<JobDistance<'a>> = jobs
+        .into_iter()
+        .filter_map(|job| {
+            Point::of(job).map(|point| JobDistance {
+                job,
+                distance_km: center.distance_km(&point),
+            })
+        })
+        .collect();
+    distances.sort_by(|a, b| a.distance_km.total_cmp(&b.distance_km));
+    distances
+}
+
+/// A cell of a grid over latitudes and longitudes, identified by its row and column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct GridCell {
+    pub row: i64,
+    pub column: i64,
+}
+
+/// Jobs grouped in one grid cell.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cluster<'a> {
+    pub cell: GridCell,
+    /// The bounds of the cell.
+    pub bounds: BoundingBox,
+    /// The mean position of the jobs in the cell.
+    pub center: Point,
+    pub jobs: Vec<&'a Job>,
+}
+
+/// Group jobs into square grid cells of `cell_size` degrees, largest clusters first.
+/// Jobs without coordinates are dropped, and there are no clusters unless the size is a positive, finite number.
+pub fn cluster<'a>(jobs: impl IntoIterator<Item = &'a Job>, cell_size: f64) -> Vec<Cluster<'a>> {
+    if !cell_size.is_finite() || cell_size <= 0.0 {
+        return Vec::new();
+    }
+    let mut cells: BTreeMap<GridCell, Vec<(&'a Job, Point)>> = BTreeMap::new();
+    for job in jobs {
+        if let Some(point) = Point::of(job) {
+            let cell = GridCell {
+                row: (point.latitude / cell_size).floor() as i64,
+                column: (point.longitude / cell_size).floor() as i64,
+            };
+            cells.entry(cell).or_default().push((job, point));
+        }
+    }
+
+    let mut clusters: Vec<Cluster<'a>> = cells
+        .into_iter()
+        .map(|(cell, jobs)| {
+            let count = jobs.len() as f64;
+            let (south, west) = (cell.row as f64 * cell_size, cell.column as f64 * cell_size);
+            Cluster {
+                cell,
+                bounds: BoundingBox::new(south, west, south + cell_size, west + cell_size),
+                center: Point::new(
+                    jobs.iter().map(|(_, point)| point.latitude).sum::<f64>() / count,
+                    jobs.iter().map(|(_, point)| point.longitude).sum::<f64>() / count,
+                ),
+                jobs: jobs.into_iter().map(|(job, _)| job).collect(),
+            }
+        })
+        .collect();
+    clusters.sort_by_key(|cluster| std::cmp::Reverse(cluster.jobs.len()));
+    clusters
+}
@@ -1,12 +1,29 @@
 #![warn(rust_2018_idioms)]
 #![doc = include_str!("../README.md")]
 pub use self::client::Client;
-pub use self::request::RequestBuilder;
+pub use self::request::{CountryRequest, RequestBuilder};
 
+pub mod analytics;
+pub mod batch;
+pub mod catalog;
 pub mod client;
+pub mod config;
+pub mod crawler;
+pub mod credentials;
+pub mod dedup;
+pub mod export;
+pub mod fanout;
+pub mod geo;
+pub mod middleware;
 pub mod models;
+pub mod profile;
+pub mod report;
 pub mod request;
+#[cfg(feature = "tracing")]
+mod trace;
+pub mod transport;
+pub mod usage;
+pub mod watcher;
 
 // TODO:
-// - rate limiter
 // - CI
@@ -0,0 +1,64 @@
+use crate::request::Error;
+use crate::transport::{HttpRequest, HttpResponse};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// A layer around every request sent by a Client.
+///
+/// Layers see outgoing requests in the order they were added, and responses in the reverse order.
+pub trait Middleware: Send + Sync {
+    /// Inspect or modify a request before it is sent.
+    fn on_request(&self, _request: &mut HttpRequest) {}
+
+    /// Inspect or modify the outcome of a request, along with the time it took.
+    fn on_response(
+        &self,
+        _request: &HttpRequest,
+        _response: &mut Result<HttpResponse, Error>,
+        _elapsed: Duration,
+    ) {
+    }
+}
+
+/// A shared layer, so it can still be inspected after being given to a Client.
+impl<T: Middleware + ?Sized> Middleware for Arc<T> {
+    fn on_request(&self, request: &mut HttpRequest) {
+        self.as_ref().on_request(request)
+    }
+
+    fn on_response(
+        &self,
+        request: &HttpRequest,
+        response: &mut Result<HttpResponse, Error>,
+        elapsed: Duration,
+    ) {
+        self.as_ref().on_response(request, response, elapsed)
+    }
+}
+
+/// Adds a header to every request, e.g. a `User-Agent`.
+#[derive(Debug, Clone)]
+pub struct SetHeader {
+    name: String,
+    value: String,
+}
+
+impl SetHeader {
+    pub fn new(name: &str, value: &str) -> Self {
+        Self {
+            name: name.into(),
+            value: value.into(),
+        }
+    }
+}
+
+impl Middleware for SetHeader {
+    fn on_request(&self, request: &mut HttpRequest) {
+        request
+            .headers
+            .retain(|(name, _)| !name.eq_ignore_ascii_case(&self.name));
+        request
+            .headers
+            .push((self.name.clone(), self.value.clone()));
+    }
+}
@@ -37,7 +37,7 @@ pub struct Company {
     /// The average salary in job advertisements posted by this company.
     /// This will normally only be provided by statistics queries, not search queries.
     /// The data may be provided with up to two decimal places, and will have no currency symbol.
-    pub average_salary: Option<usize>,
+    pub average_salary: Option<f64>,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -75,6 +75,7 @@ pub struct SalaryHistogram {
     /// Results are returned as an associative array of salaries and vacancies.
     ///  - Each salary number indicates the lower end of a range.
     ///  - Each vacancy number is the number of live job ads with a salary in range.
+    ///
     /// It can be used to generate a "histogram distribution" of salaries.
     /// This is a hashmap containing the histogram data. The buckets are the hash keys, indicating the lowest salary counted in that particular bucket.
     pub histogram: Option<HashMap<String, usize>>,
@@ -99,6 +100,130 @@ pub struct LocationDetail {
     pub display_name: Option<String>,
 }
 
+/// The maximum number of levels in a location, including the country.
+/// This matches the `location0` to `location7` query string parameters accepted by the API.
+pub const MAX_LOCATION_LEVELS: usize = 8;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LocationError {
+    /// The location does not even name a country.
+    Empty,
+    /// The location has more levels than the API accepts.
+    TooDeep(usize),
+}
+
+impl Display for LocationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LocationError::Empty => {
+                write!(f, "location has no levels")
+            }
+            LocationError::TooDeep(levels) => {
+                write!(
+                    f,
+                    "location has {levels} levels, at most {MAX_LOCATION_LEVELS} are allowed"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for LocationError {}
+
+/// A location hierarchy, made of a country followed by up to seven levels of refinement.
+/// It mirrors the `area` of a LocationDetail object, so locations returned by the API can be fed back into queries unchanged.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "Vec<String>", into = "Vec<String>")]
+pub struct Location {
+    area: Vec<String>,
+}
+
+impl Location {
+    /// Create a location covering a whole country, named as in the first element of `LocationDetail.area`, e.g. `UK`.
+    pub fn new(country: &str) -> Self {
+        Self {
+            area: vec![country.to_string()],
+        }
+    }
+
+    /// The country this location belongs to.
+    pub fn country(&self) -> &str {
+        &self.area[0]
+    }
+
+    /// The most specific level of this location.
+    pub fn name(&self) -> &str {
+        &self.area[self.area.len() - 1]
+    }
+
+    /// All the levels of this location, starting with the country.
+    pub fn area(&self) -> &[String] {
+        &self.area
+    }
+
+    /// The number of refinement levels below the country. A country on its own has a depth of 0.
+    pub fn depth(&self) -> usize {
+        self.area.len() - 1
+    }
+
+    /// The location one level up, or `None` for a country.
+    pub fn parent(&self) -> Option<Self> {
+        if self.depth() == 0 {
+            return None;
+        }
+        Some(Self {
+            area: self.area[..self.area.len() - 1].to_vec(),
+        })
+    }
+
+    /// Refine this location by one level, or `None` if it already has the maximum number of levels.
+    pub fn child(&self, name: &str) -> Option<Self> {
+        if self.area.len() >= MAX_LOCATION_LEVELS {
+            return None;
+        }
+        let mut area = self.area.clone();
+        area.push(name.to_string());
+        Some(Self { area })
+    }
+
+    /// Whether `other` lies strictly inside this location.
+    pub fn contains(&self, other: &Location) -> bool {
+        other.area.len() > self.area.len() && other.area.starts_with(&self.area)
+    }
+}
+
+impl Display for Location {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.area.join(" > "))
+    }
+}
+
+impl TryFrom<Vec<String>> for Location {
+    type Error = LocationError;
+
+    fn try_from(area: Vec<String>) -> Result<Self, Self::Error> {
+        match area.len() {
+            0 => Err(LocationError::Empty),
+            len if len > MAX_LOCATION_LEVELS => Err(LocationError::TooDeep(len)),
+            _ => Ok(Self { area }),
+        }
+    }
+}
+
+impl TryFrom<&LocationDetail> for Location {
+    type Error = LocationError;
+
+    fn try_from(detail: &LocationDetail) -> Result<Self, Self::Error> {
+        Self::try_from(detail.area.clone().unwrap_or_default())
+    }
+}
+
+impl From<Location> for Vec<String> {
+    fn from(location: Location) -> Self {
+        location.area
+    }
+}
+
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct LocationJobs {
     /// The number of jobs available at this location.
@@ -121,7 +246,7 @@ where
     Ok(buf == "1")
 }
 
-#[derive(PartialEq, Clone, Debug)]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Debug)]
 pub enum ContractType {
     Permanent,
     Contract,
@@ -142,7 +267,7 @@ where
     }
 }
 
-#[derive(PartialEq, Clone, Debug)]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Debug)]
 pub enum ContractTime {
     FullTime,
     PartTime,
@@ -219,13 +344,15 @@ pub struct JobSearchResults {
     pub mean: f64,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum SortDirection {
     Up,
     Down,
 }
 
 impl Display for SortDirection {
-    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
             SortDirection::Up => {
                 write!(f, "up")
@@ -237,6 +364,8 @@ impl Display for SortDirection {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum SortBy {
     Default,
     Hybrid,
@@ -246,7 +375,7 @@ pub enum SortBy {
 }
 
 impl Display for SortBy {
-    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
             SortBy::Default => {
                 write!(f, "default")
@@ -278,7 +407,7 @@ where
     ser.end()
 }
 
-#[derive(Debug, Default, Serialize)]
+#[derive(Debug, Default, Clone, Serialize)]
 pub struct Parameters {
     #[serde(serialize_with = "location_serialize")]
     #[serde(flatten)]
@@ -309,6 +438,26 @@ pub struct Parameters {
     pub sort_by: Option<String>,
 }
 
+impl Parameters {
+    /// Flatten the parameters into query string pairs, leaving out those which are not set.
+    pub fn to_query(&self) -> Vec<(String, String)> {
+        let value = serde_json::to_value(self).unwrap_or_default();
+        let object = match value {
+            serde_json::Value::Object(object) => object,
+            _ => return Vec::new(),
+        };
+        object
+            .into_iter()
+            .filter_map(|(key, value)| match value {
+                serde_json::Value::Null => None,
+                serde_json::Value::String(value) => Some((key, value)),
+                value => Some((key, value.to_string())),
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Country {
     UnitedKingdom,
     UnitedStates,
@@ -333,6 +482,30 @@ pub enum Country {
 }
 
 impl Country {
+    /// Every country supported by the API.
+    pub const ALL: [Country; 20] = [
+        Country::UnitedKingdom,
+        Country::UnitedStates,
+        Country::Austria,
+        Country::Australia,
+        Country::Belgium,
+        Country::Brazil,
+        Country::Canada,
+        Country::Switzerland,
+        Country::Germany,
+        Country::Spain,
+        Country::France,
+        Country::India,
+        Country::Italy,
+        Country::Mexico,
+        Country::Netherlands,
+        Country::NewZealand,
+        Country::Poland,
+        Country::Russia,
+        Country::Singapore,
+        Country::SouthAfrica,
+    ];
+
     pub fn to_code(&self) -> &'static str {
         match self {
             Country::UnitedKingdom => "gb",
@@ -350,11 +523,45 @@ impl Country {
             Country::Italy => "it",
             Country::Mexico => "mx",
             Country::Netherlands => "nl",
-            Country::NewZealand => "nl",
+            Country::NewZealand => "nz",
             Country::Poland => "pl",
             Country::Russia => "ru",
             Country::Singapore => "sg",
             Country::SouthAfrica => "za",
         }
     }
+
+    /// Look up a country by the code used in request URLs, e.g. `gb`.
+    pub fn from_code(code: &str) -> Option<Self> {
+        Country::ALL
+            .iter()
+            .copied()
+            .find(|country| country.to_code().eq_ignore_ascii_case(code))
+    }
+}
+
+impl Display for Country {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_code())
+    }
+}
+
+impl Serialize for Country {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.to_code())
+    }
+}
+
+impl<'de> Deserialize<'de> for Country {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let code = String::deserialize(deserializer)?;
+        Country::from_code(&code)
+            .ok_or_else(|| serde::de::Error::custom(format!("unknown country code `{code}`")))
+    }
 }
@@ -0,0 +1,172 @@
+use crate::analytics::jobs::{JobStats, SalarySummary};
+use crate::analytics::leaderboard::Leaderboard;
+use crate::client::Client;
+use crate::models::{Category, Country, Job, Location};
+use crate::request::{Error, RequestBuilder};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// The largest page of search results the API returns.
+const MAX_RESULTS_PER_PAGE: usize = 50;
+
+/// What is known about a company from the "top_companies" leaderboard and its job ads.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CompanyProfile {
+    pub canonical_name: String,
+    /// The display name, from the leaderboard or else from the first job ad.
+    pub display_name: Option<String>,
+    pub country: Country,
+    /// The position of the company in the leaderboard, if it is listed.
+    pub rank: Option<usize>,
+    /// The number of ads of the company according to the leaderboard.
+    pub leaderboard_count: Option<usize>,
+    /// The average salary of the company according to the leaderboard.
+    pub leaderboard_average_salary: Option<f64>,
+    /// The number of jobs matching a search for the company.
+    pub job_count: usize,
+    /// The number of jobs that were fetched and aggregated.
+    pub sampled_jobs: usize,
+    /// Salary statistics over the fetched jobs.
+    pub salaries: Option<SalarySummary>,
+    /// The number of fetched jobs per category tag.
+    pub categories: BTreeMap<String, usize>,
+    /// The number of fetched jobs per location, at the configured level of `location.area`.
+    pub locations: BTreeMap<String, usize>,
+}
+
+/// Builds a CompanyProfile by combining `top_companies` with searches filtered by company.
+pub struct CompanyProfileBuilder<'a> {
+    client: &'a Client,
+    canonical_name: String,
+    country: Country,
+    category: Option<Category>,
+    location: Option<Location>,
+    max_pages: usize,
+    results_per_page: usize,
+    location_level: usize,
+}
+
+impl<'a> CompanyProfileBuilder<'a> {
+    pub(crate) fn new(client: &'a Client, canonical_name: &str) -> Self {
+        Self {
+            client,
+            canonical_name: canonical_name.into(),
+            country: client.get_default_country(),
+            category: None,
+            location: None,
+            max_pages: 2,
+            results_per_page: MAX_RESULTS_PER_PAGE,
+            location_level: 1,
+        }
+    }
+
+    /// Profile the company in a country of interest. Defaults to the client's default country.
+    pub fn country(mut self, country: Country) -> Self {
+        self.country = country;
+        self
+    }
+
+    /// Restrict the profile to a category.
+    pub fn category(mut self, category: &Category) -> Self {
+        self.category = Some(category.clone());
+        self
+    }
+
+    /// Restrict the profile to a location.
+    pub fn location(mut self, location: &Location) -> Self {
+        self.location = Some(location.clone());
+        self
+    }
+
+    /// Set the maximum number of search pages fetched, at least 1. Defaults to 2.
+    pub fn max_pages(mut self, max_pages: usize) -> Self {
+        self.max_pages = max_pages.max(1);
+        self
+    }
+
+    /// Set the number of jobs fetched per search page, between 1 and the API's maximum of 50. Defaults to 50.
+    pub fn results_per_page(mut self, results_per_page: usize) -> Self {
+        self.results_per_page = results_per_page.clamp(1, MAX_RESULTS_PER_PAGE);
+        self
+    }
+
+    /// Set the level of `location.area` jobs are grouped by, where 0 is the country. Defaults to 1.
+    pub fn location_level(mut self, location_level: usize) -> Self {
+        self.location_level = location_level;
+        self
+    }
+
+    /// Executes the requests and aggregates the profile.
+    pub async fn fetch(&self) -> Result<CompanyProfile, Error> {
+        let mut top_companies = self
+            .client
+            .top_companies()
+            .country(self.country)
+            .without_filters();
+        if let Some(category) = &self.category {
+            top_companies = top_companies.known_category(category);
+        }
+        if let Some(location) = &self.location {
+            top_companies = top_companies.location_path(location);
+        }
+        let (leaderboard, jobs) = futures::join!(top_companies.fetch(), self.fetch_jobs());
+        let leaderboard = Leaderboard::from(&leaderboard?);
+        let (job_count, jobs) = jobs?;
+        let entry = leaderboard.get(&self.canonical_name);
+
+        let stats = JobStats::new(&jobs);
+        Ok(CompanyProfile {
+            canonical_name: self.canonical_name.clone(),
+            display_name: entry
+                .and_then(|entry| entry.company.display_name.clone())
+                .or_else(|| jobs.iter().find_map(|job| job.company.display_name.clone())),
+            country: self.country,
+            rank: entry.map(|entry| entry.rank),
+            leaderboard_count: entry.and_then(|entry| entry.company.count),
+            leaderboard_average_salary: entry.and_then(|entry| entry.company.average_salary),
+            job_count,
+            sampled_jobs: jobs.len(),
+            salaries: stats.salary_summary(),
+            categories: stats
+                .by_category()
+                .into_iter()
+                .map(|(tag, group)| (tag, group.jobs))
+                .collect(),
+            locations: stats
+                .by_location(self.location_level)
+                .into_iter()
+                .filter_map(|(location, group)| location.map(|location| (location, group.jobs)))
+                .collect(),
+        })
+    }
+
+    /// Fetch pages of jobs of the company, until a page is not full or `max_pages` is reached.
+    async fn fetch_jobs(&self) -> Result<(usize, Vec<Job>), Error> {
+        let mut jobs = Vec::new();
+        let mut job_count = 0;
+        for page in 1..=self.max_pages {
+            let mut search = self
+                .client
+                .search()
+                .country(self.country)
+                .without_filters()
+                .company(&self.canonical_name)
+                .results_per_page(self.results_per_page)
+                .page(page);
+            if let Some(category) = &self.category {
+                search = search.known_category(category);
+            }
+            if let Some(location) = &self.location {
+                search = search.location_path(location);
+            }
+            let results = search.fetch().await?;
+            job_count = results.count;
+            let full = results.results.len() >= self.results_per_page;
+            jobs.extend(results.results);
+            if !full || jobs.len() >= job_count {
+                break;
+            }
+        }
+        Ok((job_count, jobs))
+    }
+}
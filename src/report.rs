@@ -0,0 +1,192 @@
+use crate::analytics::histogram::SalaryDistribution;
+use crate::analytics::history::SalaryTimeSeries;
+use crate::client::Client;
+use crate::models::{Category, Country, Location};
+use crate::request::RequestBuilder;
+use futures::future::join_all;
+use serde::Serialize;
+use std::io;
+
+/// One line of a SalaryReport, for a category in a location.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct SalaryReportRow {
+    /// The category tag, or `None` for all categories.
+    pub category: Option<String>,
+    pub category_label: Option<String>,
+    /// The location, or `None` for the whole country.
+    pub location: Option<String>,
+    /// The number of vacancies in the salary histogram.
+    pub sample_size: usize,
+    pub mean: Option<f64>,
+    pub median: Option<f64>,
+    pub p10: Option<f64>,
+    pub p25: Option<f64>,
+    pub p75: Option<f64>,
+    pub p90: Option<f64>,
+    /// The most recent monthly average salary.
+    pub latest_average: Option<f64>,
+    /// The trend of the monthly average salary, in currency per month.
+    pub trend_per_month: Option<f64>,
+    /// Why the data could not be gathered, if a request failed.
+    pub error: Option<String>,
+}
+
+/// Salary statistics compared across categories and locations.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SalaryReport {
+    pub country: Country,
+    pub rows: Vec<SalaryReportRow>,
+}
+
+impl SalaryReport {
+    /// Write the report as JSON.
+    pub fn write_json(&self, writer: impl io::Write) -> serde_json::Result<()> {
+        serde_json::to_writer_pretty(writer, self)
+    }
+
+    /// Write the rows of the report as CSV, with a header line.
+    pub fn write_csv(&self, writer: impl io::Write) -> csv::Result<()> {
+        let mut writer = csv::Writer::from_writer(writer);
+        for row in &self.rows {
+            writer.serialize(row)?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+/// Gathers salary histograms and history for every combination of categories and locations.
+///
+/// Requests run concurrently, within the concurrency and rate limits of the client.
+/// A failed combination is kept in the report with its error, so the other rows are not lost.
+pub struct SalaryReportBuilder<'a> {
+    client: &'a Client,
+    country: Country,
+    categories: Vec<Category>,
+    locations: Vec<Location>,
+    months: Option<usize>,
+}
+
+impl<'a> SalaryReportBuilder<'a> {
+    pub(crate) fn new(client: &'a Client) -> Self {
+        Self {
+            client,
+            country: client.get_default_country(),
+            categories: Vec::new(),
+            locations: Vec::new(),
+            months: None,
+        }
+    }
+
+    /// Report on a country of interest. Defaults to the client's default country.
+    pub fn country(mut self, country: Country) -> Self {
+        self.country = country;
+        self
+    }
+
+    /// Compare a category, as returned by the "category" endpoint. Without any, all categories are combined.
+    pub fn category(mut self, category: &Category) -> Self {
+        self.categories.push(category.clone());
+        self
+    }
+
+    /// Compare several categories at once.
+    pub fn categories(mut self, categories: &[Category]) -> Self {
+        self.categories.extend_from_slice(categories);
+        self
+    }
+
+    /// Compare a location. Without any, the whole country is used.
+    pub fn location(mut self, location: &Location) -> Self {
+        self.locations.push(location.clone());
+        self
+    }
+
+    /// Set the number of months of history used for trends.
+    pub fn months(mut self, months: usize) -> Self {
+        self.months = Some(months);
+        self
+    }
+
+    /// Executes the requests and builds the report, with one row per category and location.
+    pub async fn build(&self) -> SalaryReport {
+        let categories: Vec<Option<&Category>> = if self.categories.is_empty() {
+            vec![None]
+        } else {
+            self.categories.iter().map(Some).collect()
+        };
+        let locations: Vec<Option<&Location>> = if self.locations.is_empty() {
+            vec![None]
+        } else {
+            self.locations.iter().map(Some).collect()
+        };
+        let combinations = categories
+            .iter()
+            .flat_map(|category| locations.iter().map(move |location| (*category, *location)));
+
+        let rows = join_all(combinations.map(|(category, location)| self.row(category, location)));
+        SalaryReport {
+            country: self.country,
+            rows: rows.await,
+        }
+    }
+
+    async fn row(
+        &self,
+        category: Option<&Category>,
+        location: Option<&Location>,
+    ) -> SalaryReportRow {
+        let mut histogram = self
+            .client
+            .histogram()
+            .country(self.country)
+            .without_filters();
+        let mut history = self
+            .client
+            .history()
+            .country(self.country)
+            .without_filters();
+        if let Some(category) = category {
+            histogram = histogram.known_category(category);
+            history = history.known_category(category);
+        }
+        if let Some(location) = location {
+            histogram = histogram.location_path(location);
+            history = history.location_path(location);
+        }
+        if let Some(months) = self.months {
+            history = history.months(months);
+        }
+
+        let mut row = SalaryReportRow {
+            category: category.map(|category| category.tag.clone()),
+            category_label: category.map(|category| category.label.clone()),
+            location: location.map(|location| location.to_string()),
+            ..Default::default()
+        };
+        let (histogram, history) = futures::join!(histogram.fetch(), history.fetch());
+        let (histogram, history) = match (histogram, history) {
+            (Ok(histogram), Ok(history)) => (histogram, history),
+            (Err(error), _) | (_, Err(error)) => {
+                row.error = Some(error.to_string());
+                return row;
+            }
+        };
+
+        let distribution = SalaryDistribution::from_histogram(&histogram);
+        row.sample_size = distribution.total();
+        row.mean = distribution.mean();
+        row.median = distribution.median();
+        if let Some(percentiles) = distribution.percentiles() {
+            row.p10 = Some(percentiles.p10);
+            row.p25 = Some(percentiles.p25);
+            row.p75 = Some(percentiles.p75);
+            row.p90 = Some(percentiles.p90);
+        }
+
+        let series = SalaryTimeSeries::from_history(&history);
+        row.latest_average = series.points().last().map(|point| point.salary);
+        row.trend_per_month = series.trend_slope();
+        row
+    }
+}
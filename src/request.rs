@@ -1,15 +1,32 @@
 use crate::client::Client;
+use crate::fanout::MultiCountryRequest;
 use crate::models;
+use crate::transport::{HttpRequest, HttpResponse};
 use async_trait::async_trait;
 use reqwest::StatusCode;
 use serde::de::DeserializeOwned;
-
-const ROOT_URL: &str = "https://api.adzuna.com/v1/api";
+use std::time::{Duration, Instant};
+
+pub(crate) const ROOT_URL: &str = "https://api.adzuna.com/v1/api";
+
+/// The broad cause of an Error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorKind {
+    /// The API answered with an unsuccessful status code.
+    Status,
+    /// The request could not be sent, or the response could not be received.
+    Transport,
+    /// The response did not have the expected format.
+    Decode,
+    /// The request did not complete in time.
+    Timeout,
+}
 
 #[derive(Debug)]
 pub struct Error {
     pub api_error: Option<models::ApiException>,
     pub http_status: StatusCode,
+    pub kind: ErrorKind,
 }
 
 impl Error {
@@ -17,16 +34,57 @@ impl Error {
         Self {
             api_error,
             http_status,
+            kind: ErrorKind::Status,
         }
     }
     pub fn from_status(http_status: StatusCode) -> Self {
         Self {
             api_error: None,
             http_status,
+            kind: ErrorKind::Status,
+        }
+    }
+    pub fn from_kind(kind: ErrorKind) -> Self {
+        let http_status = match kind {
+            ErrorKind::Timeout => StatusCode::REQUEST_TIMEOUT,
+            _ => StatusCode::BAD_REQUEST,
+        };
+        Self {
+            api_error: None,
+            http_status,
+            kind,
         }
     }
 }
 
+impl ErrorKind {
+    /// A short lowercase name for the kind, e.g. for metric labels.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorKind::Status => "status",
+            ErrorKind::Transport => "transport",
+            ErrorKind::Decode => "decode",
+            ErrorKind::Timeout => "timeout",
+        }
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (&self.kind, &self.api_error) {
+            (ErrorKind::Status, Some(api_error)) => {
+                write!(f, "{} ({})", api_error.display, self.http_status)
+            }
+            (ErrorKind::Status, None) => write!(f, "request failed with {}", self.http_status),
+            (ErrorKind::Transport, _) => write!(f, "request could not be completed"),
+            (ErrorKind::Decode, _) => write!(f, "response could not be decoded"),
+            (ErrorKind::Timeout, _) => write!(f, "request timed out"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
 #[async_trait]
 pub trait RequestBuilder {
     type Response: DeserializeOwned + std::fmt::Debug;
@@ -37,63 +95,178 @@ pub trait RequestBuilder {
     fn get_client(&self) -> &Client;
     #[doc(hidden)]
     fn get_parameters(&self) -> &models::Parameters;
+    #[doc(hidden)]
+    fn get_timeout(&self) -> Option<Duration> {
+        None
+    }
+    #[doc(hidden)]
+    fn get_endpoint(&self) -> &'static str {
+        "custom"
+    }
+    #[doc(hidden)]
+    fn get_country(&self) -> Option<&'static str> {
+        None
+    }
+    #[doc(hidden)]
+    fn get_page(&self) -> Option<usize> {
+        None
+    }
 
     /// Builds and executes request.
     async fn fetch(&self) -> Result<Self::Response, Error> {
-        let url = format!("{}{}", ROOT_URL, self.get_request_url());
-        let auth_params: Vec<(String, String)> = vec![
-            ("app_id".into(), self.get_client().app_id.clone()),
-            ("app_key".into(), self.get_client().app_key.clone()),
-        ];
+        let response = execute(self);
+        #[cfg(feature = "tracing")]
+        let response = tracing::Instrument::instrument(
+            response,
+            crate::trace::request_span(self.get_endpoint(), self.get_country(), self.get_page()),
+        );
+        response.await
+    }
+}
 
-        let request = self
-            .get_client()
-            .req_client
-            .get(url)
-            .query(&auth_params)
-            .query(self.get_parameters());
-
-        let response = request
-            .send()
-            .await
-            .map_err(|e| Error::from_status(e.status().unwrap_or(StatusCode::BAD_REQUEST)))?;
-        let status = response.status();
-
-        if status != StatusCode::OK {
-            return Err(Error::new(
-                response.json::<models::ApiException>().await.ok(),
-                status,
-            ));
+async fn execute<R: RequestBuilder + Sync + ?Sized>(builder: &R) -> Result<R::Response, Error> {
+    let client = builder.get_client();
+    let _permit = client.throttle().await;
+    let pool = client.credentials();
+    let parameters = builder.get_parameters().to_query();
+
+    let mut attempt = 1;
+    loop {
+        let (key, credentials) = pool.acquire();
+        let mut query: Vec<(String, String)> = vec![
+            ("app_id".into(), credentials.app_id.clone()),
+            ("app_key".into(), credentials.app_key.expose().to_string()),
+        ];
+        query.extend(parameters.iter().cloned());
+        let request = HttpRequest {
+            url: format!("{}{}", client.get_base_url(), builder.get_request_url()),
+            query,
+            headers: Vec::new(),
+            timeout: builder.get_timeout().or(client.get_timeout()),
+        };
+
+        #[cfg(feature = "tracing")]
+        tracing::Span::current().record("attempt", attempt);
+        let started = Instant::now();
+        let response = client.send(request).await;
+        let bytes_received = response.as_ref().map_or(0, |response| response.body.len());
+        let retry = match &response {
+            Ok(response) => pool.report(key, response.status) && attempt < pool.len(),
+            Err(_) => false,
+        };
+        if retry {
+            client.record_usage(
+                builder.get_endpoint(),
+                builder.get_country(),
+                started.elapsed(),
+                bytes_received,
+                Some(ErrorKind::Status),
+            );
+            attempt += 1;
+            client.wait_for_slot().await;
+            continue;
         }
 
-        response.json::<Self::Response>().await.map_err(|e| {
-            println!("{e:#?}");
-            Error::from_status(StatusCode::BAD_REQUEST)
-        })
+        let result = decode::<R::Response>(response);
+        client.record_usage(
+            builder.get_endpoint(),
+            builder.get_country(),
+            started.elapsed(),
+            bytes_received,
+            result.as_ref().err().map(|error| error.kind),
+        );
+        return result;
+    }
+}
+
+fn decode<T: DeserializeOwned>(response: Result<HttpResponse, Error>) -> Result<T, Error> {
+    let response = response?;
+
+    if response.status != StatusCode::OK {
+        return Err(Error::new(
+            serde_json::from_slice::<models::ApiException>(&response.body).ok(),
+            response.status,
+        ));
     }
+
+    serde_json::from_slice::<T>(&response.body).map_err(|_e| {
+        #[cfg(feature = "tracing")]
+        tracing::warn!(error = %_e, "failed to decode response");
+        Error::from_kind(ErrorKind::Decode)
+    })
+}
+
+/// The country a new request is sent for, unless it specifies one.
+fn default_country(client: &Client) -> &'static str {
+    client.get_default_country().to_code()
 }
 
 macro_rules! create_endpoint {
-    ($name: ident) => {
+    ($name: ident $(, $apply_defaults: ident)? $(; $($field: ident: $type: ty = $init: expr),+)?) => {
+        #[derive(Clone)]
         pub struct $name<'a> {
             client: &'a Client,
             parameters: models::Parameters,
-            search_country: &'static str,
-            search_page: usize,
+            timeout: Option<Duration>,
+            $($($field: $type,)+)?
         }
         impl<'a> $name<'a> {
             pub(crate) fn new(client: &'a Client) -> Self {
+                let parameters = models::Parameters::default();
+                $(let parameters = {
+                    let mut parameters = parameters;
+                    client.get_defaults().$apply_defaults(&mut parameters);
+                    parameters
+                };)?
                 Self {
                     client,
-                    parameters: Default::default(),
-                    search_country: models::Country::UnitedStates.to_code(),
-                    search_page: 1,
+                    parameters,
+                    timeout: None,
+                    $($($field: ($init)(client),)+)?
                 }
             }
+
+            /// Override the client's timeout for this request.
+            pub fn timeout(mut self, timeout: Duration) -> Self {
+                self.timeout = Some(timeout);
+                self
+            }
         }
     };
 }
 
+/// Requests which can be sent for any country.
+pub trait CountryRequest: RequestBuilder + Clone + Sync {
+    #[doc(hidden)]
+    fn set_country(&mut self, country: models::Country);
+
+    /// Run a copy of this request for each of the given countries.
+    fn across(self, countries: &[models::Country]) -> MultiCountryRequest<Self> {
+        MultiCountryRequest::new(self, countries)
+    }
+}
+
+macro_rules! impl_country_request {
+    ($($name: ident),*) => {
+        $(
+            impl CountryRequest for $name<'_> {
+                fn set_country(&mut self, country: models::Country) {
+                    self.search_country = country.to_code();
+                }
+            }
+        )*
+    };
+}
+
+impl_country_request!(
+    CategoriesRequest,
+    HistogramRequest,
+    HistoryRequest,
+    TopCompaniesRequest,
+    GeodataRequest,
+    SearchRequest
+);
+
 create_endpoint!(VersionRequest);
 
 impl RequestBuilder for VersionRequest<'_> {
@@ -107,12 +280,20 @@ impl RequestBuilder for VersionRequest<'_> {
         &self.parameters
     }
 
+    fn get_timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    fn get_endpoint(&self) -> &'static str {
+        "version"
+    }
+
     fn get_request_url(&self) -> String {
         "/version".into()
     }
 }
 
-create_endpoint!(CategoriesRequest);
+create_endpoint!(CategoriesRequest; search_country: &'static str = default_country);
 
 impl RequestBuilder for CategoriesRequest<'_> {
     type Response = models::Categories;
@@ -125,20 +306,32 @@ impl RequestBuilder for CategoriesRequest<'_> {
         &self.parameters
     }
 
+    fn get_timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    fn get_endpoint(&self) -> &'static str {
+        "categories"
+    }
+
+    fn get_country(&self) -> Option<&'static str> {
+        Some(self.search_country)
+    }
+
     fn get_request_url(&self) -> String {
         format!("/jobs/{}/categories", self.search_country)
     }
 }
 
 impl CategoriesRequest<'_> {
-    /// Filter with a country of interest. Defaults to US.
+    /// Filter with a country of interest. Defaults to the client's default country.
     pub fn country(mut self, country: models::Country) -> Self {
         self.search_country = country.to_code();
         self
     }
 }
 
-create_endpoint!(HistogramRequest);
+create_endpoint!(HistogramRequest, apply_filters; search_country: &'static str = default_country);
 
 impl RequestBuilder for HistogramRequest<'_> {
     type Response = models::SalaryHistogram;
@@ -151,6 +344,18 @@ impl RequestBuilder for HistogramRequest<'_> {
         &self.parameters
     }
 
+    fn get_timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    fn get_endpoint(&self) -> &'static str {
+        "histogram"
+    }
+
+    fn get_country(&self) -> Option<&'static str> {
+        Some(self.search_country)
+    }
+
     fn get_request_url(&self) -> String {
         format!("/jobs/{}/histogram", self.search_country)
     }
@@ -162,7 +367,7 @@ impl HistogramRequest<'_> {
         self.parameters.what = Some(what.into());
         self
     }
-    /// Filter with a country of interest. Defaults to US.
+    /// Filter with a country of interest. Defaults to the client's default country.
     pub fn country(mut self, country: models::Country) -> Self {
         self.search_country = country.to_code();
         self
@@ -170,20 +375,39 @@ impl HistogramRequest<'_> {
 
     /// Filter by a location, in a similar form to that returned in a LocationDetail object.
     pub fn location(mut self, location: &str) -> Self {
-        if self.parameters.locations.len() < 8 {
+        if self.parameters.locations.len() < models::MAX_LOCATION_LEVELS {
             self.parameters.locations.push(location.to_string());
         }
         self
     }
 
+    /// Filter by a whole location hierarchy, replacing any locations set previously.
+    pub fn location_path(mut self, location: &models::Location) -> Self {
+        self.parameters.locations = location.area().to_vec();
+        self
+    }
+
     /// Filter with a category tag, as returned by the "category" endpoint.
     pub fn category(mut self, category: &str) -> Self {
         self.parameters.category = Some(category.into());
         self
     }
+
+    /// Filter with a category, as found in a CategoryCatalog.
+    pub fn known_category(mut self, category: &models::Category) -> Self {
+        self.parameters.category = Some(category.tag.clone());
+        self
+    }
+
+    /// Drop the category and locations applied from the client's defaults.
+    pub(crate) fn without_filters(mut self) -> Self {
+        self.parameters.category = None;
+        self.parameters.locations.clear();
+        self
+    }
 }
 
-create_endpoint!(HistoryRequest);
+create_endpoint!(HistoryRequest, apply_filters; search_country: &'static str = default_country);
 
 impl RequestBuilder for HistoryRequest<'_> {
     type Response = models::HistoricalSalary;
@@ -196,6 +420,18 @@ impl RequestBuilder for HistoryRequest<'_> {
         &self.parameters
     }
 
+    fn get_timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    fn get_endpoint(&self) -> &'static str {
+        "history"
+    }
+
+    fn get_country(&self) -> Option<&'static str> {
+        Some(self.search_country)
+    }
+
     fn get_request_url(&self) -> String {
         format!("/jobs/{}/history", self.search_country)
     }
@@ -207,7 +443,7 @@ impl HistoryRequest<'_> {
         self.parameters.months = Some(months);
         self
     }
-    /// Filter with a country of interest. Defaults to US.
+    /// Filter with a country of interest. Defaults to the client's default country.
     pub fn country(mut self, country: models::Country) -> Self {
         self.search_country = country.to_code();
         self
@@ -215,20 +451,39 @@ impl HistoryRequest<'_> {
 
     /// Filter by a location, in a similar form to that returned in a LocationDetail object.
     pub fn location(mut self, location: &str) -> Self {
-        if self.parameters.locations.len() < 8 {
+        if self.parameters.locations.len() < models::MAX_LOCATION_LEVELS {
             self.parameters.locations.push(location.to_string());
         }
         self
     }
 
+    /// Filter by a whole location hierarchy, replacing any locations set previously.
+    pub fn location_path(mut self, location: &models::Location) -> Self {
+        self.parameters.locations = location.area().to_vec();
+        self
+    }
+
     /// Filter with a category tag, as returned by the "category" endpoint.
     pub fn category(mut self, category: &str) -> Self {
         self.parameters.category = Some(category.into());
         self
     }
+
+    /// Filter with a category, as found in a CategoryCatalog.
+    pub fn known_category(mut self, category: &models::Category) -> Self {
+        self.parameters.category = Some(category.tag.clone());
+        self
+    }
+
+    /// Drop the category and locations applied from the client's defaults.
+    pub(crate) fn without_filters(mut self) -> Self {
+        self.parameters.category = None;
+        self.parameters.locations.clear();
+        self
+    }
 }
 
-create_endpoint!(TopCompaniesRequest);
+create_endpoint!(TopCompaniesRequest, apply_filters; search_country: &'static str = default_country);
 
 impl RequestBuilder for TopCompaniesRequest<'_> {
     type Response = models::TopCompanies;
@@ -241,6 +496,18 @@ impl RequestBuilder for TopCompaniesRequest<'_> {
         &self.parameters
     }
 
+    fn get_timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    fn get_endpoint(&self) -> &'static str {
+        "top_companies"
+    }
+
+    fn get_country(&self) -> Option<&'static str> {
+        Some(self.search_country)
+    }
+
     fn get_request_url(&self) -> String {
         format!("/jobs/{}/top_companies", self.search_country)
     }
@@ -252,7 +519,7 @@ impl TopCompaniesRequest<'_> {
         self.parameters.what = Some(what.into());
         self
     }
-    /// Filter with a country of interest. Defaults to US.
+    /// Filter with a country of interest. Defaults to the client's default country.
     pub fn country(mut self, country: models::Country) -> Self {
         self.search_country = country.to_code();
         self
@@ -260,20 +527,39 @@ impl TopCompaniesRequest<'_> {
 
     /// Filter by a location, in a similar form to that returned in a LocationDetail object.
     pub fn location(mut self, location: &str) -> Self {
-        if self.parameters.locations.len() < 8 {
+        if self.parameters.locations.len() < models::MAX_LOCATION_LEVELS {
             self.parameters.locations.push(location.to_string());
         }
         self
     }
 
+    /// Filter by a whole location hierarchy, replacing any locations set previously.
+    pub fn location_path(mut self, location: &models::Location) -> Self {
+        self.parameters.locations = location.area().to_vec();
+        self
+    }
+
     /// Filter with a category tag, as returned by the "category" endpoint.
     pub fn category(mut self, category: &str) -> Self {
         self.parameters.category = Some(category.into());
         self
     }
+
+    /// Filter with a category, as found in a CategoryCatalog.
+    pub fn known_category(mut self, category: &models::Category) -> Self {
+        self.parameters.category = Some(category.tag.clone());
+        self
+    }
+
+    /// Drop the category and locations applied from the client's defaults.
+    pub(crate) fn without_filters(mut self) -> Self {
+        self.parameters.category = None;
+        self.parameters.locations.clear();
+        self
+    }
 }
 
-create_endpoint!(GeodataRequest);
+create_endpoint!(GeodataRequest, apply_filters; search_country: &'static str = default_country);
 
 impl RequestBuilder for GeodataRequest<'_> {
     type Response = models::JobGeoData;
@@ -286,6 +572,18 @@ impl RequestBuilder for GeodataRequest<'_> {
         &self.parameters
     }
 
+    fn get_timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    fn get_endpoint(&self) -> &'static str {
+        "geodata"
+    }
+
+    fn get_country(&self) -> Option<&'static str> {
+        Some(self.search_country)
+    }
+
     fn get_request_url(&self) -> String {
         format!("/jobs/{}/geodata", self.search_country)
     }
@@ -300,20 +598,36 @@ impl GeodataRequest<'_> {
 
     /// Filter by a location, in a similar form to that returned in a LocationDetail object.
     pub fn location(mut self, location: &str) -> Self {
-        if self.parameters.locations.len() < 8 {
+        if self.parameters.locations.len() < models::MAX_LOCATION_LEVELS {
             self.parameters.locations.push(location.to_string());
         }
         self
     }
 
+    /// Filter by a whole location hierarchy, replacing any locations set previously.
+    pub fn location_path(mut self, location: &models::Location) -> Self {
+        self.parameters.locations = location.area().to_vec();
+        self
+    }
+
     /// Filter with a category tag, as returned by the "category" endpoint.
     pub fn category(mut self, category: &str) -> Self {
         self.parameters.category = Some(category.into());
         self
     }
+
+    /// Filter with a category, as found in a CategoryCatalog.
+    pub fn known_category(mut self, category: &models::Category) -> Self {
+        self.parameters.category = Some(category.tag.clone());
+        self
+    }
 }
 
-create_endpoint!(SearchRequest);
+create_endpoint!(
+    SearchRequest, apply_search;
+    search_country: &'static str = default_country,
+    search_page: usize = |_| 1
+);
 
 impl RequestBuilder for SearchRequest<'_> {
     type Response = models::JobSearchResults;
@@ -326,13 +640,29 @@ impl RequestBuilder for SearchRequest<'_> {
         &self.parameters
     }
 
+    fn get_timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    fn get_endpoint(&self) -> &'static str {
+        "search"
+    }
+
+    fn get_country(&self) -> Option<&'static str> {
+        Some(self.search_country)
+    }
+
+    fn get_page(&self) -> Option<usize> {
+        Some(self.search_page)
+    }
+
     fn get_request_url(&self) -> String {
         format!("/jobs/{}/search/{}", self.search_country, self.search_page)
     }
 }
 
 impl SearchRequest<'_> {
-    /// Filter with a country of interest. Defaults to US.
+    /// Filter with a country of interest. Defaults to the client's default country.
     pub fn country(mut self, country: models::Country) -> Self {
         self.search_country = country.to_code();
         self
@@ -340,18 +670,37 @@ impl SearchRequest<'_> {
 
     /// Filter by a location, in a similar form to that returned in a LocationDetail object.
     pub fn location(mut self, location: &str) -> Self {
-        if self.parameters.locations.len() < 8 {
+        if self.parameters.locations.len() < models::MAX_LOCATION_LEVELS {
             self.parameters.locations.push(location.to_string());
         }
         self
     }
 
+    /// Filter by a whole location hierarchy, replacing any locations set previously.
+    pub fn location_path(mut self, location: &models::Location) -> Self {
+        self.parameters.locations = location.area().to_vec();
+        self
+    }
+
     /// Filter with a category tag, as returned by the "category" endpoint.
     pub fn category(mut self, category: &str) -> Self {
         self.parameters.category = Some(category.into());
         self
     }
 
+    /// Filter with a category, as found in a CategoryCatalog.
+    pub fn known_category(mut self, category: &models::Category) -> Self {
+        self.parameters.category = Some(category.tag.clone());
+        self
+    }
+
+    /// Drop the category and locations applied from the client's defaults.
+    pub(crate) fn without_filters(mut self) -> Self {
+        self.parameters.category = None;
+        self.parameters.locations.clear();
+        self
+    }
+
     /// Set the page for search results.
     pub fn page(mut self, page: usize) -> Self {
         if page > 0 {
@@ -0,0 +1,55 @@
+use crate::request::Error;
+use crate::transport::{HttpRequest, HttpResponse};
+use std::time::Duration;
+use tracing::field::Empty;
+use tracing::Span;
+
+/// Query string parameters which must never be logged.
+const SECRET_PARAMETERS: [&str; 2] = ["app_id", "app_key"];
+
+pub(crate) fn request_span(
+    endpoint: &'static str,
+    country: Option<&'static str>,
+    page: Option<usize>,
+) -> Span {
+    tracing::info_span!(
+        "adzuna_request",
+        endpoint,
+        country,
+        page,
+        attempt = Empty,
+        status = Empty,
+        latency_ms = Empty,
+    )
+}
+
+pub(crate) fn record_request(request: &HttpRequest) {
+    let query: Vec<(&str, &str)> = request
+        .query
+        .iter()
+        .map(|(key, value)| {
+            if SECRET_PARAMETERS.contains(&key.as_str()) {
+                (key.as_str(), "[redacted]")
+            } else {
+                (key.as_str(), value.as_str())
+            }
+        })
+        .collect();
+    tracing::debug!(url = %request.url, ?query, "sending request");
+}
+
+/// Records the outcome of a request on the current span.
+pub(crate) fn record_response(response: &Result<HttpResponse, Error>, elapsed: Duration) {
+    let span = Span::current();
+    span.record("latency_ms", elapsed.as_millis() as u64);
+    match response {
+        Ok(response) => {
+            span.record("status", response.status.as_u16());
+            tracing::debug!(status = response.status.as_u16(), "received response");
+        }
+        Err(error) => {
+            span.record("status", error.http_status.as_u16());
+            tracing::warn!(kind = ?error.kind, "request failed");
+        }
+    }
+}
@@ -0,0 +1,163 @@
+use crate::request::{Error, ErrorKind};
+use async_trait::async_trait;
+use reqwest::StatusCode;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// An outgoing GET request to the API.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HttpRequest {
+    /// The full URL, without the query string.
+    pub url: String,
+    /// The query string parameters, in order.
+    pub query: Vec<(String, String)>,
+    /// Extra headers to send.
+    pub headers: Vec<(String, String)>,
+    /// The time allowed for the whole request, if limited.
+    pub timeout: Option<Duration>,
+}
+
+/// A response received from the API.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HttpResponse {
+    pub status: StatusCode,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl HttpResponse {
+    /// Create a response with a status code and body, and no headers.
+    pub fn new(status: StatusCode, body: impl Into<Vec<u8>>) -> Self {
+        Self {
+            status,
+            headers: Vec::new(),
+            body: body.into(),
+        }
+    }
+}
+
+/// Sends requests on behalf of a Client.
+///
+/// Failing to get any response should be reported with an error of kind `Transport` or `Timeout`;
+/// unsuccessful status codes are returned as responses and handled by the caller.
+/// The Client enforces `HttpRequest::timeout` itself, so transports need not.
+#[async_trait]
+pub trait HttpTransport: Send + Sync {
+    async fn send(&self, request: HttpRequest) -> Result<HttpResponse, Error>;
+}
+
+/// A shared transport, so it can still be inspected after being given to a Client.
+#[async_trait]
+impl<T: HttpTransport + ?Sized> HttpTransport for Arc<T> {
+    async fn send(&self, request: HttpRequest) -> Result<HttpResponse, Error> {
+        self.as_ref().send(request).await
+    }
+}
+
+/// The default transport, backed by a `reqwest::Client`.
+#[derive(Debug, Clone, Default)]
+pub struct ReqwestTransport {
+    client: reqwest::Client,
+}
+
+impl ReqwestTransport {
+    pub fn new(client: reqwest::Client) -> Self {
+        Self { client }
+    }
+}
+
+fn reqwest_error(error: reqwest::Error) -> Error {
+    if error.is_timeout() {
+        return Error::from_kind(ErrorKind::Timeout);
+    }
+    Error {
+        kind: ErrorKind::Transport,
+        ..Error::from_status(error.status().unwrap_or(StatusCode::BAD_REQUEST))
+    }
+}
+
+#[async_trait]
+impl HttpTransport for ReqwestTransport {
+    async fn send(&self, request: HttpRequest) -> Result<HttpResponse, Error> {
+        let mut builder = self.client.get(&request.url).query(&request.query);
+        for (name, value) in &request.headers {
+            builder = builder.header(name, value);
+        }
+        if let Some(timeout) = request.timeout {
+            builder = builder.timeout(timeout);
+        }
+
+        let response = builder.send().await.map_err(reqwest_error)?;
+        let status = response.status();
+        let headers = response
+            .headers()
+            .iter()
+            .filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.to_string())))
+            .collect();
+        let body = response.bytes().await.map_err(reqwest_error)?.to_vec();
+
+        Ok(HttpResponse {
+            status,
+            headers,
+            body,
+        })
+    }
+}
+
+/// An in-memory transport returning canned responses, for tests.
+///
+/// Responses are matched by the end of the request URL, e.g. `/jobs/gb/categories`.
+/// Several responses queued for the same path are returned in turn, and the last one is repeated.
+/// Requests to unknown paths receive a 404.
+#[derive(Debug, Default)]
+pub struct StubTransport {
+    routes: Mutex<HashMap<String, VecDeque<HttpResponse>>>,
+    requests: Mutex<Vec<HttpRequest>>,
+}
+
+impl StubTransport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queue a response for requests whose URL ends with `path`.
+    pub fn respond(self, path: &str, response: HttpResponse) -> Self {
+        self.routes
+            .lock()
+            .unwrap()
+            .entry(path.to_string())
+            .or_default()
+            .push_back(response);
+        self
+    }
+
+    /// Queue a successful JSON response for requests whose URL ends with `path`.
+    pub fn respond_json(self, path: &str, body: &serde_json::Value) -> Self {
+        self.respond(path, HttpResponse::new(StatusCode::OK, body.to_string()))
+    }
+
+    /// Every request sent so far, in order.
+    pub fn requests(&self) -> Vec<HttpRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl HttpTransport for StubTransport {
+    async fn send(&self, request: HttpRequest) -> Result<HttpResponse, Error> {
+        let mut routes = self.routes.lock().unwrap();
+        let queue = routes
+            .iter_mut()
+            .filter(|(path, _)| request.url.ends_with(path.as_str()))
+            .max_by_key(|(path, _)| path.len())
+            .map(|(_, queue)| queue);
+        let response = match queue {
+            Some(queue) if queue.len() > 1 => queue.pop_front(),
+            Some(queue) => queue.front().cloned(),
+            None => None,
+        };
+        self.requests.lock().unwrap().push(request);
+        Ok(response.unwrap_or_else(|| HttpResponse::new(StatusCode::NOT_FOUND, Vec::new())))
+    }
+}
@@ -0,0 +1,122 @@
+use crate::request::ErrorKind;
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Counters for a group of requests.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RequestStats {
+    /// The number of requests sent.
+    pub requests: u64,
+    /// The number of failed requests, by the kind of error.
+    pub errors: HashMap<ErrorKind, u64>,
+    /// The total size of the response bodies received.
+    pub bytes_received: u64,
+    /// The time spent waiting for responses, summed over all requests.
+    pub total_latency: Duration,
+    /// The time taken by the slowest request.
+    pub max_latency: Duration,
+}
+
+impl RequestStats {
+    /// The number of failed requests, whatever the kind of error.
+    pub fn error_count(&self) -> u64 {
+        self.errors.values().sum()
+    }
+
+    /// The average time taken by a request.
+    pub fn mean_latency(&self) -> Duration {
+        if self.requests == 0 {
+            return Duration::ZERO;
+        }
+        self.total_latency / self.requests as u32
+    }
+
+    fn record(&mut self, latency: Duration, bytes_received: usize, error: Option<ErrorKind>) {
+        self.requests += 1;
+        self.bytes_received += bytes_received as u64;
+        self.total_latency += latency;
+        self.max_latency = self.max_latency.max(latency);
+        if let Some(kind) = error {
+            *self.errors.entry(kind).or_default() += 1;
+        }
+    }
+
+    fn merge(&mut self, other: &RequestStats) {
+        self.requests += other.requests;
+        self.bytes_received += other.bytes_received;
+        self.total_latency += other.total_latency;
+        self.max_latency = self.max_latency.max(other.max_latency);
+        for (kind, count) in &other.errors {
+            *self.errors.entry(*kind).or_default() += count;
+        }
+    }
+}
+
+/// A snapshot of the requests made by a Client, broken down by endpoint and country code.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct UsageStats {
+    /// Counters keyed by endpoint name and country code.
+    /// The version endpoint is not tied to a country, so its country is `None`.
+    pub by_endpoint: HashMap<(&'static str, Option<&'static str>), RequestStats>,
+}
+
+impl UsageStats {
+    /// Counters over every request.
+    pub fn total(&self) -> RequestStats {
+        self.sum(|_, _| true)
+    }
+
+    /// Counters for one endpoint, e.g. `search`, over every country.
+    pub fn endpoint(&self, endpoint: &str) -> RequestStats {
+        self.sum(|e, _| e == endpoint)
+    }
+
+    /// Counters for one country code, e.g. `gb`, over every endpoint.
+    pub fn country(&self, country: &str) -> RequestStats {
+        self.sum(|_, c| c == Some(country))
+    }
+
+    pub(crate) fn record(
+        &mut self,
+        endpoint: &'static str,
+        country: Option<&'static str>,
+        latency: Duration,
+        bytes_received: usize,
+        error: Option<ErrorKind>,
+    ) {
+        self.by_endpoint
+            .entry((endpoint, country))
+            .or_default()
+            .record(latency, bytes_received, error);
+
+        #[cfg(feature = "metrics")]
+        {
+            let country = country.unwrap_or("none");
+            metrics::counter!("adzuna_requests_total", "endpoint" => endpoint, "country" => country)
+                .increment(1);
+            metrics::counter!("adzuna_response_bytes_total", "endpoint" => endpoint, "country" => country)
+                .increment(bytes_received as u64);
+            metrics::histogram!("adzuna_request_duration_seconds", "endpoint" => endpoint, "country" => country)
+                .record(latency.as_secs_f64());
+            if let Some(kind) = error {
+                metrics::counter!(
+                    "adzuna_errors_total",
+                    "endpoint" => endpoint,
+                    "country" => country,
+                    "kind" => kind.as_str()
+                )
+                .increment(1);
+            }
+        }
+    }
+
+    fn sum(&self, filter: impl Fn(&str, Option<&str>) -> bool) -> RequestStats {
+        let mut total = RequestStats::default();
+        for ((endpoint, country), stats) in &self.by_endpoint {
+            if filter(endpoint, *country) {
+                total.merge(stats);
+            }
+        }
+        total
+    }
+}
@@ -0,0 +1,269 @@
+use crate::models::{Job, SortBy, SortDirection};
+use crate::request::{Error, RequestBuilder, SearchRequest};
+use async_trait::async_trait;
+use futures::stream::{self, Stream};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet, VecDeque};
+use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+#[derive(Debug)]
+pub enum WatchError {
+    /// A search request failed.
+    Request(Error),
+    /// The state could not be loaded or saved.
+    Store(std::io::Error),
+}
+
+impl Display for WatchError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WatchError::Request(error) => {
+                write!(f, "search failed: {error}")
+            }
+            WatchError::Store(error) => {
+                write!(f, "failed to access watcher state: {error}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for WatchError {}
+
+/// What a JobWatcher remembers between polls and restarts.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WatchState {
+    /// The ids of the jobs seen at or after `last_created`, with their creation dates.
+    pub seen: BTreeMap<String, String>,
+    /// The creation date of the newest job seen, as an ISO 8601 date time string.
+    pub last_created: Option<String>,
+}
+
+/// Where a JobWatcher keeps its state.
+#[async_trait]
+pub trait StateStore: Send + Sync {
+    async fn load(&self) -> std::io::Result<WatchState>;
+    async fn save(&self, state: &WatchState) -> std::io::Result<()>;
+}
+
+/// A shared store, so it can outlive a JobWatcher.
+#[async_trait]
+impl<T: StateStore + ?Sized> StateStore for Arc<T> {
+    async fn load(&self) -> std::io::Result<WatchState> {
+        self.as_ref().load().await
+    }
+
+    async fn save(&self, state: &WatchState) -> std::io::Result<()> {
+        self.as_ref().save(state).await
+    }
+}
+
+/// Keeps the state in memory, so it is lost when the process exits.
+#[derive(Debug, Default)]
+pub struct MemoryStore {
+    state: Mutex<WatchState>,
+}
+
+impl MemoryStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl StateStore for MemoryStore {
+    async fn load(&self) -> std::io::Result<WatchState> {
+        Ok(self.state.lock().unwrap().clone())
+    }
+
+    async fn save(&self, state: &WatchState) -> std::io::Result<()> {
+        *self.state.lock().unwrap() = state.clone();
+        Ok(())
+    }
+}
+
+/// Keeps the state in a JSON file. A missing file is treated as an empty state.
+#[derive(Debug, Clone)]
+pub struct JsonFileStore {
+    path: PathBuf,
+}
+
+impl JsonFileStore {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+#[async_trait]
+impl StateStore for JsonFileStore {
+    async fn load(&self) -> std::io::Result<WatchState> {
+        match tokio::fs::read(&self.path).await {
+            Ok(contents) => Ok(serde_json::from_slice(&contents)?),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(WatchState::default()),
+            Err(error) => Err(error),
+        }
+    }
+
+    async fn save(&self, state: &WatchState) -> std::io::Result<()> {
+        // Write to a temporary file first, so a crash never leaves a truncated state behind.
+        let temporary = self.path.with_extension("tmp");
+        tokio::fs::write(&temporary, serde_json::to_vec(state)?).await?;
+        tokio::fs::rename(&temporary, &self.path).await
+    }
+}
+
+/// Polls a search and reports the jobs which appeared since the previous poll.
+///
+/// The search is sorted by date, newest first, and paged through until jobs older than the newest one already seen are reached.
+/// Jobs created before that point are never reported, even if they only show up later.
+pub struct JobWatcher<'a, S> {
+    search: SearchRequest<'a>,
+    store: S,
+    interval: Duration,
+    max_pages: usize,
+    skip_existing: bool,
+    state: Option<WatchState>,
+}
+
+impl<'a, S: StateStore> JobWatcher<'a, S> {
+    pub fn new(search: SearchRequest<'a>, store: S) -> Self {
+        Self {
+            search: search.sort_by(SortBy::Date).sort_dir(SortDirection::Down),
+            store,
+            interval: Duration::from_secs(300),
+            max_pages: 5,
+            skip_existing: false,
+            state: None,
+        }
+    }
+
+    /// Set the time between polls when streaming. Defaults to 5 minutes.
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Set the maximum number of pages of new jobs fetched per poll. Defaults to 5.
+    /// Any new jobs beyond them are reported by the following polls.
+    pub fn max_pages(mut self, max_pages: usize) -> Self {
+        if max_pages > 0 {
+            self.max_pages = max_pages;
+        }
+        self
+    }
+
+    /// When starting without any saved state, record the current jobs without reporting them.
+    pub fn skip_existing(mut self) -> Self {
+        self.skip_existing = true;
+        self
+    }
+
+    /// Poll the search once, returning the new jobs, newest first.
+    pub async fn poll(&mut self) -> Result<Vec<Job>, WatchError> {
+        let mut state = match self.state.take() {
+            Some(state) => state,
+            None => self.store.load().await.map_err(WatchError::Store)?,
+        };
+        let first_run = state.last_created.is_none() && state.seen.is_empty();
+        let watermark = state.last_created.clone();
+
+        // Jobs are only marked as seen once every page has been fetched, so a failed poll
+        // leaves the state untouched and the next poll reports them again.
+        let mut new_jobs: Vec<Job> = Vec::new();
+        let mut found = HashSet::new();
+        let mut reached_watermark = false;
+        // Pages holding only jobs reported by earlier polls don't count towards max_pages,
+        // so jobs left over when a poll runs out of pages are picked up by the next one.
+        let mut fresh_pages = 0;
+        let mut page = 0;
+        while fresh_pages < self.max_pages {
+            page += 1;
+            let results = self
+                .search
+                .clone()
+                .page(page)
+                .fetch()
+                .await
+                .map_err(WatchError::Request);
+            let results = match results {
+                Ok(results) => results,
+                Err(error) => {
+                    self.state = Some(state);
+                    return Err(error);
+                }
+            };
+
+            reached_watermark = results.results.is_empty();
+            let reported = new_jobs.len();
+            for job in results.results {
+                if watermark.as_ref().is_some_and(|w| job.created < *w) {
+                    reached_watermark = true;
+                    continue;
+                }
+                if state.seen.contains_key(&job.id) || !found.insert(job.id.clone()) {
+                    continue;
+                }
+                new_jobs.push(job);
+            }
+            if new_jobs.len() > reported {
+                fresh_pages += 1;
+            }
+            if reached_watermark {
+                break;
+            }
+        }
+
+        for job in &new_jobs {
+            state.seen.insert(job.id.clone(), job.created.clone());
+        }
+        // Moving the watermark past jobs which were not reached yet would skip them for good.
+        if reached_watermark || watermark.is_none() {
+            if let Some(newest) = new_jobs.iter().map(|job| &job.created).max() {
+                if state.last_created.as_ref().is_none_or(|last| newest > last) {
+                    state.last_created = Some(newest.clone());
+                }
+            }
+        }
+        if let Some(last_created) = &state.last_created {
+            state.seen.retain(|_, created| *created >= *last_created);
+        }
+
+        let saved = self.store.save(&state).await;
+        self.state = Some(state);
+        saved.map_err(WatchError::Store)?;
+
+        if first_run && self.skip_existing {
+            return Ok(Vec::new());
+        }
+        Ok(new_jobs)
+    }
+
+    /// Poll the search forever, yielding each new job as it is discovered.
+    /// An error is yielded when a poll fails, and polling resumes after the next interval.
+    pub fn stream(self) -> impl Stream<Item = Result<Job, WatchError>> + 'a
+    where
+        S: 'a,
+    {
+        let pending: VecDeque<Result<Job, WatchError>> = VecDeque::new();
+        stream::unfold(
+            (self, pending, true),
+            |(mut watcher, mut pending, mut first)| async move {
+                loop {
+                    if let Some(item) = pending.pop_front() {
+                        return Some((item, (watcher, pending, first)));
+                    }
+                    if !first {
+                        tokio::time::sleep(watcher.interval).await;
+                    }
+                    first = false;
+                    match watcher.poll().await {
+                        Ok(jobs) => pending.extend(jobs.into_iter().map(Ok)),
+                        Err(error) => pending.push_back(Err(error)),
+                    }
+                }
+            },
+        )
+    }
+}
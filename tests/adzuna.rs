@@ -1,11 +1,14 @@
 #[cfg(test)]
 mod tests {
-    use std::env;
+    use std::time::Duration;
 
-    use adzuna::{Client, RequestBuilder};
+    use adzuna::batch::AnyResponse;
+    use adzuna::models::Country;
+    use adzuna::request::ErrorKind;
+    use adzuna::{Client, CountryRequest, RequestBuilder};
 
     fn get_client() -> Client {
-        Client::new(env::var("API_ID").unwrap(), env::var("API_KEY").unwrap())
+        Client::from_env().unwrap()
     }
 
     #[tokio::test]
@@ -60,6 +63,22 @@ mod tests {
         assert!(!geodata.is_empty());
     }
 
+    #[tokio::test]
+    async fn it_crawls_location_tree() {
+        let client = get_client();
+        let tree = client
+            .location_tree()
+            .max_depth(2)
+            .max_requests(2)
+            .crawl()
+            .await
+            .unwrap();
+        assert_eq!(tree.requests, 2);
+        assert!(!tree.root.children.is_empty());
+        let region = &tree.root.children[0];
+        assert_eq!(region.location.parent().unwrap(), tree.root.location);
+    }
+
     #[tokio::test]
     async fn it_fetches_history() {
         let client = get_client();
@@ -81,6 +100,35 @@ mod tests {
         assert!(jobs.count != 0)
     }
 
+    #[tokio::test]
+    async fn it_searches_across_countries() {
+        let client = get_client().max_concurrency(2);
+        let jobs = client
+            .search()
+            .what("developer")
+            .results_per_page(1)
+            .across(&[Country::UnitedKingdom, Country::Canada])
+            .fetch()
+            .await;
+        assert!(jobs.is_complete());
+        assert!(jobs.results.contains_key(&Country::UnitedKingdom));
+        assert!(jobs.results.contains_key(&Country::Canada));
+    }
+
+    #[tokio::test]
+    async fn it_fetches_a_batch_in_order() {
+        let client = get_client();
+        let results = client
+            .batch::<AnyResponse>()
+            .request(client.api_version())
+            .request(client.histogram().what("excel"))
+            .concurrency(2)
+            .fetch()
+            .await;
+        assert!(matches!(results[0], Ok(AnyResponse::Version(_))));
+        assert!(matches!(results[1], Ok(AnyResponse::Histogram(_))));
+    }
+
     #[tokio::test]
     async fn it_limits_search_results() {
         let client = get_client();
@@ -124,6 +172,18 @@ mod tests {
         assert_eq!(error.http_status, 401);
     }
 
+    #[tokio::test]
+    async fn it_times_out() {
+        let client = get_client().timeout(Duration::from_secs(30));
+        let jobs = client
+            .search()
+            .what("engineer")
+            .timeout(Duration::from_millis(1))
+            .fetch()
+            .await;
+        assert_eq!(jobs.unwrap_err().kind, ErrorKind::Timeout);
+    }
+
     #[tokio::test]
     async fn it_fails_with_invalid_category() {
         let client = get_client();
@@ -0,0 +1,269 @@
+#[cfg(test)]
+mod tests {
+    use adzuna::analytics::histogram::SalaryDistribution;
+    use adzuna::analytics::history::{SalaryTimeSeries, YearMonth, YearMonthError};
+    use adzuna::analytics::jobs::{DateInterval, JobStats};
+    use adzuna::analytics::leaderboard::Leaderboard;
+    use adzuna::models::{
+        Category, Company, ContractType, HistoricalSalary, Job, LocationDetail, SalaryHistogram,
+        TopCompanies,
+    };
+    use std::collections::HashMap;
+
+    fn approx(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-6
+    }
+
+    fn histogram() -> SalaryHistogram {
+        let buckets = [
+            ("20000", 10),
+            ("40000", 20),
+            ("30000", 60),
+            ("50000", 10),
+            ("n/a", 5),
+        ];
+        SalaryHistogram {
+            histogram: Some(
+                buckets
+                    .iter()
+                    .map(|(lower, vacancies)| (lower.to_string(), *vacancies))
+                    .collect::<HashMap<_, _>>(),
+            ),
+        }
+    }
+
+    #[test]
+    fn it_sorts_and_bounds_buckets() {
+        let distribution = SalaryDistribution::from_histogram(&histogram());
+        let lowers: Vec<f64> = distribution.buckets().iter().map(|b| b.lower).collect();
+        assert_eq!(lowers, [20000.0, 30000.0, 40000.0, 50000.0]);
+        assert_eq!(distribution.buckets()[0].upper, Some(30000.0));
+        assert_eq!(distribution.buckets()[3].upper, None);
+        assert_eq!(distribution.total(), 100);
+        assert_eq!(distribution.mode().unwrap().lower, 30000.0);
+    }
+
+    #[test]
+    fn it_computes_statistics() {
+        let distribution = SalaryDistribution::from_histogram(&histogram());
+        // Midpoints 25k, 35k, 45k and 55k weighted by 10, 60, 20 and 10 vacancies.
+        assert!(approx(distribution.mean().unwrap(), 38000.0));
+        // The median falls 40 vacancies into the 60 of the 30k-40k bucket.
+        assert!(approx(
+            distribution.median().unwrap(),
+            30000.0 + 40.0 / 60.0 * 10000.0
+        ));
+        let percentiles = distribution.percentiles().unwrap();
+        assert!(approx(percentiles.p10, 30000.0));
+        assert!(approx(percentiles.p90, 50000.0));
+        assert!(percentiles.p25 < percentiles.p50 && percentiles.p50 < percentiles.p75);
+    }
+
+    #[test]
+    fn it_ranks_salaries() {
+        let distribution = SalaryDistribution::from_histogram(&histogram());
+        assert!(approx(distribution.percentile_rank(10000.0).unwrap(), 0.0));
+        assert!(approx(distribution.percentile_rank(35000.0).unwrap(), 40.0));
+        assert!(approx(
+            distribution.percentile_rank(70000.0).unwrap(),
+            100.0
+        ));
+        assert!(SalaryDistribution::default().percentile_rank(1.0).is_none());
+    }
+
+    fn history() -> HistoricalSalary {
+        let months = [
+            ("2023-11", 30000.0),
+            ("2023-12", 30500.0),
+            ("2024-02", 31500.0),
+            ("2024-01", 31000.0),
+            ("2024-04", 32500.0),
+            ("2024-11", 33000.0),
+            ("bogus", 1.0),
+        ];
+        HistoricalSalary {
+            month: Some(
+                months
+                    .iter()
+                    .map(|(month, salary)| (month.to_string(), *salary))
+                    .collect(),
+            ),
+        }
+    }
+
+    #[test]
+    fn it_orders_history_and_finds_gaps() {
+        let series = SalaryTimeSeries::from_history(&history());
+        assert_eq!(series.len(), 6);
+        assert_eq!(series.points()[0].month.to_string(), "2023-11");
+        assert_eq!(series.points()[5].month.to_string(), "2024-11");
+        let gaps: Vec<String> = series.gaps().iter().map(|m| m.to_string()).collect();
+        assert_eq!(
+            gaps,
+            ["2024-03", "2024-05", "2024-06", "2024-07", "2024-08", "2024-09", "2024-10"]
+        );
+    }
+
+    #[test]
+    fn it_computes_changes_and_trend() {
+        let series = SalaryTimeSeries::from_history(&history());
+        let month_over_month = series.month_over_month();
+        assert_eq!(month_over_month.len(), 3);
+        assert_eq!(month_over_month[1].month, YearMonth::new(2024, 1).unwrap());
+        assert!(approx(month_over_month[1].absolute, 500.0));
+
+        let year_over_year = series.year_over_year();
+        assert_eq!(year_over_year.len(), 1);
+        assert!(approx(year_over_year[0].percent, 10.0));
+
+        let rolling = series.rolling_average(3);
+        assert!(approx(rolling[3].salary, 31000.0));
+
+        // The first five months rise by 500 a month.
+        let early = SalaryTimeSeries::new(series.points()[..5].to_vec());
+        assert!(approx(early.trend_slope().unwrap(), 500.0));
+        let forecast = early.forecast(2);
+        assert_eq!(forecast[0].month.to_string(), "2024-05");
+        assert!(approx(forecast[1].salary, 33500.0));
+    }
+
+    fn job(created: &str, tag: &str, city: &str, salary: f64, predicted: bool) -> Job {
+        Job {
+            created: created.into(),
+            category: Category {
+                tag: tag.into(),
+                ..Default::default()
+            },
+            location: LocationDetail {
+                area: Some(vec!["UK".into(), city.into()]),
+                display_name: None,
+            },
+            company: Company {
+                display_name: Some("Acme".into()),
+                ..Default::default()
+            },
+            salary_min: salary,
+            salary_max: salary,
+            salary_is_predicted: predicted,
+            contract_type: (tag == "it-jobs").then_some(ContractType::Permanent),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn it_aggregates_jobs() {
+        let jobs = [
+            job("2024-01-05T09:00:00Z", "it-jobs", "London", 50000.0, false),
+            job("2024-01-20T09:00:00Z", "it-jobs", "Leeds", 30000.0, false),
+            job(
+                "2024-02-01T09:00:00Z",
+                "sales-jobs",
+                "London",
+                90000.0,
+                true,
+            ),
+            job("2024-02-02T09:00:00Z", "sales-jobs", "London", 0.0, false),
+        ];
+        let stats = JobStats::new(&jobs);
+        let summary = stats.salary_summary().unwrap();
+        assert_eq!(summary.count, 3);
+        assert_eq!(summary.median, 50000.0);
+        assert_eq!(stats.salary_distribution(20000.0).total(), 3);
+        for width in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+            assert_eq!(stats.salary_distribution(width).total(), 0);
+        }
+
+        let advertised = JobStats::new(&jobs).include_predicted(false);
+        assert_eq!(advertised.salary_summary().unwrap().mean, 40000.0);
+
+        let by_category = advertised.by_category();
+        assert_eq!(by_category["sales-jobs"].jobs, 2);
+        assert_eq!(by_category["sales-jobs"].mean_salary, None);
+        assert_eq!(by_category["it-jobs"].mean_salary, Some(40000.0));
+
+        assert_eq!(
+            stats.by_contract_type()[&Some(ContractType::Permanent)].jobs,
+            2
+        );
+        assert_eq!(stats.by_company()[&Some("Acme".to_string())].jobs, 4);
+        assert_eq!(stats.by_location(1)[&Some("London".to_string())].jobs, 3);
+        assert_eq!(stats.by_location(2)[&None].jobs, 4);
+
+        let months = stats.posting_dates(DateInterval::Month);
+        assert_eq!(
+            months.into_iter().collect::<Vec<_>>(),
+            [("2024-01".into(), 2), ("2024-02".into(), 2)]
+        );
+    }
+
+    fn company(name: &str, count: usize, average_salary: f64) -> Company {
+        Company {
+            display_name: Some(name.to_uppercase()),
+            canonical_name: Some(name.into()),
+            count: Some(count),
+            average_salary: Some(average_salary),
+        }
+    }
+
+    #[test]
+    fn it_diffs_leaderboards() {
+        let before = Leaderboard::from(&TopCompanies {
+            leaderboard: Some(vec![
+                company("acme", 30, 40000.0),
+                company("initech", 20, 50000.0),
+                company("globex", 10, 30000.0),
+            ]),
+        });
+        let after = Leaderboard::new(vec![
+            company("initech", 35, 52000.0),
+            company("umbrella", 25, 45000.0),
+            company("acme", 15, 40000.0),
+        ]);
+        let diff = before.diff(&after);
+        assert_eq!(diff.changes.len(), 2);
+        assert_eq!(diff.changes[0].key, "initech");
+        assert_eq!(diff.changes[0].movement(), 1);
+        assert_eq!(diff.changes[0].count_delta, Some(15));
+        assert_eq!(diff.changes[0].average_salary_delta, Some(2000.0));
+        assert_eq!(diff.changes[1].movement(), -2);
+        assert_eq!(diff.entrants[0].key, "umbrella");
+        assert_eq!(diff.dropouts[0].key, "globex");
+    }
+
+    #[test]
+    fn it_combines_leaderboards() {
+        let london = Leaderboard::new(vec![
+            company("acme", 30, 40000.0),
+            company("globex", 10, 30000.0),
+        ]);
+        let leeds = Leaderboard::new(vec![
+            company("globex", 30, 34000.0),
+            company("initech", 5, 50000.0),
+        ]);
+        let combined = Leaderboard::combine([&london, &leeds]);
+        let keys: Vec<&str> = combined
+            .entries()
+            .iter()
+            .map(|entry| entry.key.as_str())
+            .collect();
+        assert_eq!(keys, ["globex", "acme", "initech"]);
+        let globex = &combined.get("globex").unwrap().company;
+        assert_eq!(globex.count, Some(40));
+        assert_eq!(globex.average_salary, Some(33000.0));
+        assert_eq!(globex.display_name.as_deref(), Some("GLOBEX"));
+    }
+
+    #[test]
+    fn it_parses_months() {
+        assert_eq!("2013-09".parse(), Ok(YearMonth::new(2013, 9).unwrap()));
+        assert_eq!("2013".parse::<YearMonth>(), Err(YearMonthError::Format));
+        assert_eq!(
+            "year-09".parse::<YearMonth>(),
+            Err(YearMonthError::InvalidYear)
+        );
+        assert_eq!(
+            "2013-13".parse::<YearMonth>(),
+            Err(YearMonthError::InvalidMonth)
+        );
+    }
+}
@@ -0,0 +1,69 @@
+#[cfg(all(test, feature = "arrow"))]
+mod tests {
+    use adzuna::export::arrow::{self, JobParquetWriter};
+    use adzuna::models::{ContractType, Job, LocationDetail, SalaryHistogram};
+    use arrow_array::{Array, ListArray, StringArray};
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+    use std::collections::HashMap;
+    use std::fs::File;
+
+    fn job(id: &str) -> Job {
+        Job {
+            id: id.into(),
+            location: LocationDetail {
+                area: Some(vec!["UK".into(), "London".into()]),
+                display_name: Some("London".into()),
+            },
+            contract_type: Some(ContractType::Permanent),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn it_converts_jobs_to_record_batches() {
+        let batch = arrow::jobs_to_record_batch(&[job("1"), Job::default()]).unwrap();
+        assert_eq!(batch.schema(), arrow::job_schema());
+        assert_eq!(batch.num_rows(), 2);
+
+        let contract_type = batch.column_by_name("contract_type").unwrap();
+        let contract_type = contract_type
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap();
+        assert_eq!(contract_type.value(0), "permanent");
+        assert!(contract_type.is_null(1));
+
+        let area = batch.column_by_name("location_area").unwrap();
+        let area = area.as_any().downcast_ref::<ListArray>().unwrap();
+        assert_eq!(area.value(0).len(), 2);
+        assert!(area.is_null(1));
+
+        let histogram = SalaryHistogram {
+            histogram: Some(HashMap::from([("20000".into(), 3), ("30000".into(), 5)])),
+        };
+        let batch = arrow::histogram_to_record_batch(&histogram).unwrap();
+        assert_eq!(batch.num_rows(), 2);
+        assert_eq!(batch.column_by_name("upper").unwrap().null_count(), 1);
+    }
+
+    #[test]
+    fn it_writes_jobs_to_parquet() {
+        let path = std::env::temp_dir().join(format!("adzuna-jobs-{}.parquet", std::process::id()));
+        let mut writer = JobParquetWriter::new(File::create(&path).unwrap()).unwrap();
+        writer.write(&[job("1"), job("2")]).unwrap();
+        writer.write(&[job("3")]).unwrap();
+        writer.close().unwrap();
+
+        let reader = ParquetRecordBatchReaderBuilder::try_new(File::open(&path).unwrap())
+            .unwrap()
+            .build()
+            .unwrap();
+        let batches: Vec<_> = reader.map(|batch| batch.unwrap()).collect();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(
+            batches.iter().map(|batch| batch.num_rows()).sum::<usize>(),
+            3
+        );
+        assert_eq!(batches[0].schema(), arrow::job_schema());
+    }
+}
@@ -0,0 +1,132 @@
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use adzuna::batch::{AnyResponse, BoxedRequest};
+    use adzuna::models::SalaryHistogram;
+    use adzuna::request::{Error, ErrorKind};
+    use adzuna::transport::{HttpRequest, HttpResponse, HttpTransport};
+    use adzuna::Client;
+    use async_trait::async_trait;
+    use futures::StreamExt;
+    use reqwest::StatusCode;
+    use serde_json::json;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    struct SlowTransport;
+
+    #[async_trait]
+    impl HttpTransport for SlowTransport {
+        async fn send(&self, _request: HttpRequest) -> Result<HttpResponse, Error> {
+            tokio::time::sleep(Duration::from_secs(60)).await;
+            Err(Error::from_kind(ErrorKind::Transport))
+        }
+    }
+
+    #[tokio::test]
+    async fn it_times_out_each_request() {
+        let client = Client::new("id".into(), "key".into()).transport(SlowTransport);
+        let histograms: Vec<BoxedRequest<'_, SalaryHistogram>> = vec![
+            Box::new(client.histogram().what("rust")),
+            Box::new(client.histogram().what("go")),
+        ];
+        let batch = client
+            .batch::<AnyResponse>()
+            .requests(histograms)
+            .request(client.top_companies().what("rust"))
+            .timeout(Duration::from_millis(10));
+        assert_eq!(batch.len(), 3);
+
+        let results = batch.fetch().await;
+        assert_eq!(results.len(), 3);
+        for result in results {
+            assert_eq!(result.unwrap_err().kind, ErrorKind::Timeout);
+        }
+    }
+
+    /// Answers histograms after waiting as many milliseconds as the `what` keywords,
+    /// and records how many requests were in flight at once.
+    #[derive(Default)]
+    struct DelayTransport {
+        in_flight: AtomicUsize,
+        max_in_flight: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl HttpTransport for DelayTransport {
+        async fn send(&self, request: HttpRequest) -> Result<HttpResponse, Error> {
+            let what = request
+                .query
+                .iter()
+                .find(|(key, _)| key == "what")
+                .map(|(_, value)| value.clone())
+                .unwrap_or_default();
+            let in_flight = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            self.max_in_flight.fetch_max(in_flight, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(what.parse().unwrap_or(0))).await;
+            self.in_flight.fetch_sub(1, Ordering::SeqCst);
+
+            let body = json!({ "histogram": { what: 1 } });
+            Ok(HttpResponse::new(StatusCode::OK, body.to_string()))
+        }
+    }
+
+    fn delayed_client() -> (Client, Arc<DelayTransport>) {
+        let transport = Arc::new(DelayTransport::default());
+        let client = Client::new("id".into(), "key".into()).transport(transport.clone());
+        (client, transport)
+    }
+
+    fn what(histogram: &SalaryHistogram) -> &str {
+        histogram.histogram.as_ref().unwrap().keys().next().unwrap()
+    }
+
+    #[tokio::test]
+    async fn it_returns_results_in_submission_order() {
+        let (client, _) = delayed_client();
+        let results = client
+            .batch::<SalaryHistogram>()
+            .request(client.histogram().what("60"))
+            .request(client.histogram().what("10"))
+            .request(client.histogram().what("30"))
+            .fetch()
+            .await;
+
+        let order: Vec<_> = results
+            .iter()
+            .map(|result| what(result.as_ref().unwrap()))
+            .collect();
+        assert_eq!(order, ["60", "10", "30"]);
+    }
+
+    #[tokio::test]
+    async fn it_streams_results_as_they_complete() {
+        let (client, _) = delayed_client();
+        let results: Vec<_> = client
+            .batch::<SalaryHistogram>()
+            .request(client.histogram().what("60"))
+            .request(client.histogram().what("10"))
+            .request(client.histogram().what("30"))
+            .stream_unordered()
+            .collect()
+            .await;
+
+        let indices: Vec<_> = results.iter().map(|(index, _)| *index).collect();
+        assert_eq!(indices, [1, 2, 0]);
+        assert_eq!(what(results[0].1.as_ref().unwrap()), "10");
+    }
+
+    #[tokio::test]
+    async fn it_caps_requests_in_flight() {
+        let (client, transport) = delayed_client();
+        let mut batch = client.batch::<SalaryHistogram>().concurrency(2);
+        for _ in 0..6 {
+            batch = batch.request(client.histogram().what("10"));
+        }
+        let results = batch.fetch().await;
+
+        assert!(results.iter().all(Result::is_ok));
+        assert_eq!(transport.max_in_flight.load(Ordering::SeqCst), 2);
+    }
+}
@@ -0,0 +1,61 @@
+#[cfg(test)]
+mod tests {
+    use adzuna::catalog::CategoryCatalog;
+    use adzuna::models::{Category, Country};
+    use adzuna::{Client, RequestBuilder};
+
+    fn catalog() -> CategoryCatalog {
+        let categories = [
+            ("it-jobs", "IT Jobs"),
+            ("accounting-finance-jobs", "Accounting & Finance Jobs"),
+            ("healthcare-nursing-jobs", "Healthcare & Nursing Jobs"),
+            ("teaching-jobs", "Teaching Jobs"),
+        ]
+        .iter()
+        .map(|(tag, label)| Category {
+            tag: tag.to_string(),
+            label: label.to_string(),
+        })
+        .collect();
+        CategoryCatalog::new(Country::UnitedKingdom, categories)
+    }
+
+    #[test]
+    fn it_looks_up_categories() {
+        let catalog = catalog();
+        assert_eq!(catalog.get("it-jobs").unwrap().label, "IT Jobs");
+        assert!(catalog.get("IT-JOBS").is_none());
+        assert_eq!(
+            catalog.find_label("teaching JOBS").unwrap().tag,
+            "teaching-jobs"
+        );
+        assert_eq!(catalog.resolve("IT Jobs").unwrap().tag, "it-jobs");
+    }
+
+    #[test]
+    fn it_suggests_misspelt_categories() {
+        let catalog = catalog();
+        let suggestions = catalog.suggest("teachng-jobs", 3);
+        assert_eq!(suggestions[0].tag, "teaching-jobs");
+        assert!(catalog.suggest("plumbing", 3).is_empty());
+    }
+
+    #[test]
+    fn it_round_trips_through_json() {
+        let catalog = catalog();
+        let json = serde_json::to_string(&catalog).unwrap();
+        assert!(json.contains("\"country\":\"gb\""));
+        let cached: CategoryCatalog = serde_json::from_str(&json).unwrap();
+        assert_eq!(cached, catalog);
+    }
+
+    #[test]
+    fn it_filters_with_known_category() {
+        let client = Client::new("id".into(), "key".into());
+        let catalog = catalog();
+        let search = client
+            .search()
+            .known_category(catalog.get("it-jobs").unwrap());
+        assert_eq!(search.get_parameters().category.as_deref(), Some("it-jobs"));
+    }
+}
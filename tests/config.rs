@@ -0,0 +1,132 @@
+#[cfg(test)]
+mod tests {
+    use adzuna::config::{ClientConfig, ConfigError, Defaults};
+    use adzuna::credentials::Credentials;
+    use adzuna::models::Country;
+    use adzuna::RequestBuilder;
+    use std::time::Duration;
+
+    #[test]
+    fn it_loads_toml() {
+        let config = ClientConfig::from_toml(
+            r#"
+            base_url = "http://localhost:8080/api/"
+            timeout_secs = 2.5
+
+            [defaults]
+            country = "gb"
+
+            [[credentials]]
+            app_id = "first"
+            app_key = "secret-one"
+
+            [[credentials]]
+            app_id = "second"
+            app_key = "secret-two"
+
+            [rate_limit]
+            requests = 25
+            period_secs = 60
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.credentials.len(), 2);
+        assert_eq!(config.defaults.country, Some(Country::UnitedKingdom));
+
+        let client = config.build().unwrap();
+        assert_eq!(client.credentials().len(), 2);
+        assert_eq!(client.get_base_url(), "http://localhost:8080/api");
+        assert_eq!(client.get_timeout(), Some(Duration::from_millis(2500)));
+        assert_eq!(client.get_default_country(), Country::UnitedKingdom);
+        assert_eq!(client.categories().get_request_url(), "/jobs/gb/categories");
+    }
+
+    #[test]
+    fn it_loads_json() {
+        let config = ClientConfig::from_json(
+            r#"{ "credentials": [{ "app_id": "id", "app_key": "key" }], "max_concurrency": 4 }"#,
+        )
+        .unwrap();
+        assert_eq!(config.max_concurrency, Some(4));
+        assert!(config.build().is_ok());
+    }
+
+    #[test]
+    fn it_rejects_invalid_config() {
+        assert!(matches!(
+            ClientConfig::from_toml("").unwrap().build(),
+            Err(ConfigError::Missing("credentials"))
+        ));
+        assert!(matches!(
+            ClientConfig::from_toml("[defaults]\ncountry = \"xx\""),
+            Err(ConfigError::Parse(_))
+        ));
+        assert!(matches!(
+            ClientConfig::from_file("does/not/exist.toml"),
+            Err(ConfigError::Io(_))
+        ));
+    }
+
+    #[test]
+    fn it_redacts_secrets() {
+        let credentials = Credentials::new("id".into(), "very-secret".into());
+        assert!(!format!("{credentials:?}").contains("very-secret"));
+        assert_eq!(credentials.app_key.to_string(), "[redacted]");
+        assert_eq!(credentials.app_key.expose(), "very-secret");
+    }
+
+    #[test]
+    fn it_applies_defaults_to_new_requests() {
+        let config = ClientConfig::from_toml(
+            r#"
+            [[credentials]]
+            app_id = "id"
+            app_key = "key"
+
+            [defaults]
+            country = "gb"
+            category = "it-jobs"
+            location = ["UK", "London"]
+            results_per_page = 50
+            sort_by = "date"
+            sort_dir = "down"
+            "#,
+        )
+        .unwrap();
+        let client = config.build().unwrap();
+
+        let search = client.search();
+        let parameters = search.get_parameters();
+        assert_eq!(search.get_request_url(), "/jobs/gb/search/1");
+        assert_eq!(parameters.category.as_deref(), Some("it-jobs"));
+        assert_eq!(parameters.locations, ["UK", "London"]);
+        assert_eq!(parameters.results_per_page, Some(50));
+        assert_eq!(parameters.sort_by.as_deref(), Some("date"));
+        assert_eq!(parameters.sort_dir.as_deref(), Some("down"));
+
+        let histogram = client.histogram();
+        assert_eq!(histogram.get_parameters().results_per_page, None);
+        assert_eq!(
+            histogram.get_parameters().category.as_deref(),
+            Some("it-jobs")
+        );
+
+        let search = client
+            .search()
+            .country(Country::Canada)
+            .category("teaching-jobs")
+            .location("Camden")
+            .results_per_page(10);
+        let parameters = search.get_parameters();
+        assert_eq!(search.get_request_url(), "/jobs/ca/search/1");
+        assert_eq!(parameters.category.as_deref(), Some("teaching-jobs"));
+        assert_eq!(parameters.locations, ["UK", "London", "Camden"]);
+        assert_eq!(parameters.results_per_page, Some(10));
+
+        let client = client.defaults(Defaults {
+            results_per_page: Some(0),
+            ..Default::default()
+        });
+        assert_eq!(client.search().get_parameters().results_per_page, None);
+    }
+}
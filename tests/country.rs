@@ -0,0 +1,13 @@
+#[cfg(test)]
+mod tests {
+    use adzuna::models::Country;
+
+    #[test]
+    fn it_round_trips_country_codes() {
+        assert_eq!(Country::NewZealand.to_code(), "nz");
+        assert_eq!(Country::from_code("NZ"), Some(Country::NewZealand));
+        for country in Country::ALL {
+            assert_eq!(Country::from_code(country.to_code()), Some(country));
+        }
+    }
+}
@@ -0,0 +1,172 @@
+#[cfg(test)]
+mod tests {
+    use adzuna::config::Defaults;
+    use adzuna::models::{Country, Location};
+    use adzuna::transport::StubTransport;
+    use adzuna::Client;
+    use serde_json::{json, Value};
+    use std::sync::Arc;
+
+    fn geodata(locations: &[(&[&str], usize)]) -> Value {
+        let locations: Vec<Value> = locations
+            .iter()
+            .map(|(area, count)| json!({ "location": { "area": area }, "count": count }))
+            .collect();
+        json!({ "locations": locations })
+    }
+
+    fn stub() -> Arc<StubTransport> {
+        let path = "/jobs/gb/geodata";
+        Arc::new(
+            StubTransport::new()
+                .respond_json(
+                    path,
+                    &geodata(&[
+                        (&["UK", "London"], 10),
+                        (&["UK", "South East"], 20),
+                        (&["UK", "London"], 10),
+                        (&["UK", "Scotland", "Glasgow"], 5),
+                    ]),
+                )
+                .respond_json(
+                    path,
+                    &geodata(&[
+                        (&["UK", "London", "Camden"], 4),
+                        (&["UK", "London", "Hackney"], 6),
+                        (&["UK", "South East", "Surrey"], 20),
+                    ]),
+                )
+                .respond_json(path, &geodata(&[(&["UK", "South East", "Surrey"], 20)])),
+        )
+    }
+
+    fn location(area: &[&str]) -> Location {
+        Location::try_from(area.iter().map(|a| a.to_string()).collect::<Vec<_>>()).unwrap()
+    }
+
+    fn parent_of(query: &[(String, String)]) -> Option<&str> {
+        query
+            .iter()
+            .rev()
+            .find(|(key, _)| key.starts_with("location"))
+            .map(|(_, value)| value.as_str())
+    }
+
+    #[tokio::test]
+    async fn it_crawls_breadth_first() {
+        let stub = stub();
+        let client = Client::new("id".into(), "key".into()).transport(stub.clone());
+        let tree = client
+            .location_tree()
+            .country(Country::UnitedKingdom)
+            .crawl()
+            .await
+            .unwrap();
+
+        assert_eq!(tree.requests, 3);
+        assert!(tree.complete);
+        assert_eq!(tree.root.location, Location::new("UK"));
+        assert_eq!(tree.root.count, Some(30));
+        let children: Vec<&str> = tree
+            .root
+            .children
+            .iter()
+            .map(|c| c.location.name())
+            .collect();
+        assert_eq!(children, ["London", "South East"]);
+        let london = tree.root.find(&location(&["UK", "London"])).unwrap();
+        assert_eq!(london.children.len(), 2);
+        let south_east = tree.root.find(&location(&["UK", "South East"])).unwrap();
+        assert_eq!(south_east.children[0].count, Some(20));
+        assert!(south_east.children[0].children.is_empty());
+
+        let parents: Vec<Option<String>> = stub
+            .requests()
+            .iter()
+            .map(|request| parent_of(&request.query).map(String::from))
+            .collect();
+        assert_eq!(
+            parents,
+            [None, Some("London".into()), Some("South East".into())]
+        );
+    }
+
+    #[tokio::test]
+    async fn it_stops_at_the_request_budget() {
+        let stub = stub();
+        let client = Client::new("id".into(), "key".into()).transport(stub.clone());
+        let tree = client
+            .location_tree()
+            .country(Country::UnitedKingdom)
+            .max_requests(2)
+            .crawl()
+            .await
+            .unwrap();
+
+        assert_eq!(tree.requests, 2);
+        assert!(!tree.complete);
+        assert_eq!(stub.requests().len(), 2);
+        let south_east = tree.root.find(&location(&["UK", "South East"])).unwrap();
+        assert!(south_east.children.is_empty());
+
+        let shallow = client
+            .location_tree()
+            .country(Country::UnitedKingdom)
+            .max_depth(1)
+            .crawl()
+            .await
+            .unwrap();
+        assert_eq!(shallow.requests, 1);
+        assert!(shallow.complete);
+
+        let clamped = client
+            .location_tree()
+            .country(Country::UnitedKingdom)
+            .max_depth(0)
+            .crawl()
+            .await
+            .unwrap();
+        assert_eq!(clamped.requests, 1);
+        assert_eq!(clamped.root.children.len(), shallow.root.children.len());
+    }
+
+    #[tokio::test]
+    async fn it_names_an_empty_root_after_the_api_area() {
+        let stub = StubTransport::new().respond_json("/jobs/gb/geodata", &geodata(&[]));
+        let client = Client::new("id".into(), "key".into()).transport(stub);
+        let tree = client
+            .location_tree()
+            .country(Country::UnitedKingdom)
+            .crawl()
+            .await
+            .unwrap();
+        assert_eq!(tree.root.location, Location::new("UK"));
+        assert_eq!(tree.root.count, Some(0));
+        assert!(tree.root.children.is_empty());
+    }
+
+    #[tokio::test]
+    async fn it_filters_with_the_default_category() {
+        let stub = Arc::new(StubTransport::new().respond_json("/jobs/gb/geodata", &geodata(&[])));
+        let client = Client::new("id".into(), "key".into())
+            .transport(stub.clone())
+            .defaults(Defaults {
+                category: Some("it-jobs".into()),
+                ..Default::default()
+            });
+        client
+            .location_tree()
+            .country(Country::UnitedKingdom)
+            .crawl()
+            .await
+            .unwrap();
+
+        let categories: Vec<_> = stub.requests()[0]
+            .query
+            .iter()
+            .filter(|(key, _)| key == "category")
+            .map(|(_, value)| value.clone())
+            .collect();
+        assert_eq!(categories, ["it-jobs"]);
+    }
+}
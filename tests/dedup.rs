@@ -0,0 +1,63 @@
+#[cfg(test)]
+mod tests {
+    use adzuna::dedup::{Deduplicator, DuplicateReason};
+    use adzuna::models::{Company, Job, LocationDetail};
+
+    fn job(id: &str, title: &str, company: &str, salary: f64) -> Job {
+        Job {
+            id: id.into(),
+            title: title.into(),
+            company: Company {
+                display_name: Some(company.into()),
+                ..Default::default()
+            },
+            location: LocationDetail {
+                area: Some(vec!["UK".into(), "London".into()]),
+                display_name: Some("London".into()),
+            },
+            salary_min: salary,
+            salary_max: salary,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn it_removes_duplicates_across_pages() {
+        let mut dedup = Deduplicator::new();
+        let first_page = dedup.deduplicate(vec![
+            job("1", "Rust Developer", "Acme Ltd", 60000.0),
+            job("2", "Data Analyst", "Acme Ltd", 40000.0),
+        ]);
+        assert_eq!(first_page.len(), 2);
+
+        let second_page = dedup.deduplicate(vec![
+            job("2", "Data Analyst", "Acme Ltd", 40000.0),
+            job("3", "Rust developer (Full Time)", "ACME LTD.", 60200.0),
+            job("4", "Rust Developer", "Acme Ltd", 75000.0),
+        ]);
+        let ids: Vec<&str> = second_page.iter().map(|job| job.id.as_str()).collect();
+        assert_eq!(ids, ["4"]);
+        assert_eq!(dedup.len(), 3);
+
+        let duplicates = dedup.duplicates();
+        assert_eq!(duplicates.len(), 2);
+        assert_eq!(duplicates[0].reason, DuplicateReason::SameId);
+        assert_eq!(duplicates[1].reason, DuplicateReason::SimilarListing);
+        assert_eq!(duplicates[1].kept_id, "1");
+        assert_eq!(duplicates[1].duplicate_id, "3");
+    }
+
+    #[test]
+    fn it_keeps_listings_without_company_or_salary() {
+        let mut dedup = Deduplicator::new();
+        let jobs = dedup.deduplicate(vec![
+            job("1", "Cleaner", "", 0.0),
+            job("2", "Cleaner", "", 0.0),
+            job("3", "Cleaner", "", 20000.0),
+            job("4", "Cleaner", "", 20000.0),
+        ]);
+        let ids: Vec<&str> = jobs.iter().map(|job| job.id.as_str()).collect();
+        assert_eq!(ids, ["1", "2", "3"]);
+        assert_eq!(dedup.duplicates()[0].kept_id, "3");
+    }
+}
@@ -0,0 +1,172 @@
+#[cfg(test)]
+mod tests {
+    use adzuna::crawler::{LocationNode, LocationTree};
+    use adzuna::export::csv::{self, JobCsvWriter};
+    use adzuna::export::geojson::{self, JobFeatures};
+    use adzuna::export::JobField;
+    use adzuna::geo::Point;
+    use adzuna::models::{
+        Company, HistoricalSalary, Job, JobGeoData, Location, LocationDetail, LocationJobs,
+        SalaryHistogram, TopCompanies,
+    };
+    use serde_json::Value;
+    use std::collections::HashMap;
+
+    fn job(id: &str, latitude: f64, longitude: f64) -> Job {
+        Job {
+            id: id.into(),
+            title: "Engineer".into(),
+            latitude,
+            longitude,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn it_exports_jobs_as_geojson() {
+        let mut out = Vec::new();
+        JobFeatures::new()
+            .properties(&[JobField::Id, JobField::Title])
+            .write(&mut out, &[job("1", 51.5, -0.1), job("2", 0.0, 0.0)])
+            .unwrap();
+        let collection: Value = serde_json::from_slice(&out).unwrap();
+        assert_eq!(collection["type"], "FeatureCollection");
+        let features = collection["features"].as_array().unwrap();
+        assert_eq!(features.len(), 2);
+        assert_eq!(
+            features[0]["geometry"]["coordinates"],
+            serde_json::json!([-0.1, 51.5])
+        );
+        assert_eq!(
+            features[0]["properties"],
+            serde_json::json!({ "id": "1", "title": "Engineer" })
+        );
+        assert!(features[1]["geometry"].is_null());
+    }
+
+    #[test]
+    fn it_exports_locations_with_counts() {
+        let london = Location::new("UK").child("London").unwrap();
+        let locate =
+            |location: &Location| (location.name() == "London").then(|| Point::new(51.5, -0.1));
+
+        let geodata = JobGeoData {
+            locations: Some(vec![LocationJobs {
+                count: Some(42),
+                location: Some(LocationDetail {
+                    area: Some(london.area().to_vec()),
+                    display_name: Some("London".into()),
+                }),
+            }]),
+        };
+        let mut out = Vec::new();
+        geojson::write_geodata(&mut out, &geodata, locate).unwrap();
+        let collection: Value = serde_json::from_slice(&out).unwrap();
+        assert_eq!(collection["features"][0]["properties"]["count"], 42);
+        assert_eq!(
+            collection["features"][0]["properties"]["location"],
+            "UK > London"
+        );
+
+        let child = LocationNode {
+            location: london,
+            display_name: Some("London".into()),
+            count: Some(42),
+            children: Vec::new(),
+        };
+        let tree = LocationTree {
+            country: "gb".into(),
+            root: LocationNode {
+                location: Location::new("UK"),
+                display_name: None,
+                count: Some(42),
+                children: vec![child],
+            },
+            requests: 1,
+            complete: true,
+        };
+        let mut out = Vec::new();
+        geojson::write_location_tree(&mut out, &tree, locate).unwrap();
+        let collection: Value = serde_json::from_slice(&out).unwrap();
+        let features = collection["features"].as_array().unwrap();
+        assert_eq!(features.len(), 2);
+        assert!(features[0]["geometry"].is_null());
+        assert_eq!(features[1]["properties"]["depth"], 1);
+        assert_eq!(features[1]["geometry"]["type"], "Point");
+    }
+
+    #[test]
+    fn it_streams_jobs_as_csv() {
+        let mut london = job("1", 51.5, -0.1);
+        london.location.area = Some(vec!["UK".into(), "London".into()]);
+        london.company.display_name = Some("Acme, Inc".into());
+
+        let mut writer = JobCsvWriter::new(Vec::new()).columns(&[
+            JobField::Id,
+            JobField::Company,
+            JobField::Area(1),
+            JobField::ContractType,
+        ]);
+        writer.write(&london).unwrap();
+        writer.write_all(&[job("2", 0.0, 0.0)]).unwrap();
+        let out = String::from_utf8(writer.into_inner().unwrap()).unwrap();
+        assert_eq!(
+            out,
+            "id,company,area_1,contract_type\n1,\"Acme, Inc\",London,\n2,,,\n"
+        );
+
+        let mut writer = JobCsvWriter::new(Vec::new());
+        writer.write(&london).unwrap();
+        let out = String::from_utf8(writer.into_inner().unwrap()).unwrap();
+        let header = out.lines().next().unwrap();
+        assert!(header.contains(",area_0,area_1,area_2,area_3,area_4,"));
+        assert!(!header.contains("location_area"));
+    }
+
+    #[test]
+    fn it_writes_statistics_as_csv() {
+        let histogram = SalaryHistogram {
+            histogram: Some(HashMap::from([("30000".into(), 5), ("20000".into(), 3)])),
+        };
+        let mut out = Vec::new();
+        csv::write_histogram(&mut out, &histogram).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "lower,upper,vacancies\n20000,30000,3\n30000,,5\n"
+        );
+
+        let mut out = Vec::new();
+        csv::write_histogram(&mut out, &SalaryHistogram::default()).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "lower,upper,vacancies\n");
+
+        let empty = JobCsvWriter::new(Vec::new()).columns(&[JobField::Id, JobField::Title]);
+        assert_eq!(empty.into_inner().unwrap(), b"id,title\n");
+
+        let history = HistoricalSalary {
+            month: Some(HashMap::from([
+                ("2024-02".into(), 31000.5),
+                ("2024-01".into(), 30000.0),
+            ])),
+        };
+        let mut out = Vec::new();
+        csv::write_history(&mut out, &history).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "month,salary\n2024-01,30000\n2024-02,31000.5\n"
+        );
+
+        let top_companies = TopCompanies {
+            leaderboard: Some(vec![Company {
+                canonical_name: Some("acme".into()),
+                count: Some(12),
+                ..Default::default()
+            }]),
+        };
+        let mut out = Vec::new();
+        csv::write_leaderboard(&mut out, &top_companies).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "rank,canonical_name,display_name,count,average_salary\n1,acme,,12,\n"
+        );
+    }
+}
@@ -0,0 +1,81 @@
+#[cfg(test)]
+mod tests {
+    use adzuna::models::Country;
+    use adzuna::request::Error;
+    use adzuna::transport::{HttpRequest, HttpResponse, HttpTransport};
+    use adzuna::{Client, CountryRequest};
+    use async_trait::async_trait;
+    use reqwest::StatusCode;
+    use serde_json::json;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+    use std::time::{Duration, Instant};
+
+    /// Answers histograms with one vacancy per letter of the country code, failing for France,
+    /// and records how many requests were in flight at once.
+    #[derive(Default)]
+    struct CountingTransport {
+        in_flight: AtomicUsize,
+        max_in_flight: AtomicUsize,
+        sent: Mutex<Vec<Instant>>,
+    }
+
+    #[async_trait]
+    impl HttpTransport for CountingTransport {
+        async fn send(&self, request: HttpRequest) -> Result<HttpResponse, Error> {
+            self.sent.lock().unwrap().push(Instant::now());
+            let in_flight = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            self.max_in_flight.fetch_max(in_flight, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            self.in_flight.fetch_sub(1, Ordering::SeqCst);
+
+            if request.url.contains("/jobs/fr/") {
+                return Ok(HttpResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "{}"));
+            }
+            let code = request.url.split('/').nth_back(1).unwrap_or_default();
+            let body = json!({ "histogram": { code: 1 } });
+            Ok(HttpResponse::new(StatusCode::OK, body.to_string()))
+        }
+    }
+
+    const COUNTRIES: [Country; 4] = [
+        Country::UnitedKingdom,
+        Country::France,
+        Country::Germany,
+        Country::Spain,
+    ];
+
+    #[tokio::test]
+    async fn it_keys_results_by_country() {
+        let transport = Arc::new(CountingTransport::default());
+        let client = Client::new("id".into(), "key".into())
+            .transport(transport.clone())
+            .max_concurrency(2);
+        let histograms = client.histogram().across(&COUNTRIES).fetch().await;
+
+        assert_eq!(histograms.results.len(), 3);
+        assert_eq!(histograms.errors.len(), 1);
+        assert_eq!(histograms.errors[&Country::France].http_status, 500);
+        for country in [Country::UnitedKingdom, Country::Germany, Country::Spain] {
+            let histogram = histograms.results[&country].histogram.as_ref().unwrap();
+            assert!(histogram.contains_key(country.to_code()));
+        }
+        assert_eq!(transport.max_in_flight.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn it_respects_the_rate_limit() {
+        let transport = Arc::new(CountingTransport::default());
+        let client = Client::new("id".into(), "key".into())
+            .transport(transport.clone())
+            .rate_limit(20, Duration::from_secs(1));
+        let histograms = client.histogram().across(&COUNTRIES[..3]).fetch().await;
+        assert_eq!(histograms.results.len() + histograms.errors.len(), 3);
+
+        let mut sent = transport.sent.lock().unwrap().clone();
+        sent.sort();
+        for pair in sent.windows(2) {
+            assert!(pair[1] - pair[0] >= Duration::from_millis(45));
+        }
+    }
+}
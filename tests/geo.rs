@@ -0,0 +1,60 @@
+#[cfg(test)]
+mod tests {
+    use adzuna::geo::{self, BoundingBox, Point};
+    use adzuna::models::Job;
+
+    fn job(id: &str, latitude: f64, longitude: f64) -> Job {
+        Job {
+            id: id.into(),
+            latitude,
+            longitude,
+            ..Default::default()
+        }
+    }
+
+    fn ids(jobs: &[&Job]) -> Vec<String> {
+        jobs.iter().map(|job| job.id.clone()).collect()
+    }
+
+    #[test]
+    fn it_computes_distances() {
+        let london = Point::new(51.5074, -0.1278);
+        let paris = Point::new(48.8566, 2.3522);
+        assert!((london.distance_km(&paris) - 343.6).abs() < 1.0);
+        assert_eq!(london.distance_km(&london), 0.0);
+        assert!(Point::of(&job("unknown", 0.0, 0.0)).is_none());
+    }
+
+    #[test]
+    fn it_filters_and_sorts_jobs() {
+        let jobs = [
+            job("reading", 51.4543, -0.9781),
+            job("city", 51.5155, -0.0922),
+            job("manchester", 53.4808, -2.2426),
+            job("unknown", 0.0, 0.0),
+        ];
+        let westminster = Point::new(51.4995, -0.1248);
+
+        let nearby = geo::within_radius(&jobs, &westminster, 60.0);
+        let nearby: Vec<&Job> = nearby.iter().map(|distance| distance.job).collect();
+        assert_eq!(ids(&nearby), ["city", "reading"]);
+        assert_eq!(geo::by_distance(&jobs, &westminster).len(), 3);
+
+        let south_east = BoundingBox::new(51.0, -1.0, 52.0, 0.5);
+        assert_eq!(
+            ids(&geo::within_bounds(&jobs, &south_east)),
+            ["reading", "city"]
+        );
+        let pacific = BoundingBox::new(-10.0, 170.0, 10.0, -170.0);
+        assert!(pacific.contains(&Point::new(0.0, 179.0)));
+        assert!(!pacific.contains(&Point::new(0.0, 0.0)));
+
+        assert_eq!(geo::cluster(&jobs, 0.5).len(), 3);
+        for size in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+            assert!(geo::cluster(&jobs, size).is_empty());
+        }
+        let clusters = geo::cluster(&jobs, 1.0);
+        assert_eq!(clusters.len(), 2);
+        assert_eq!(ids(&clusters[0].jobs), ["reading", "city"]);
+    }
+}
@@ -0,0 +1,63 @@
+#[cfg(test)]
+mod tests {
+    use adzuna::models::{Location, LocationDetail, LocationError};
+    use adzuna::{Client, RequestBuilder};
+
+    fn detail(area: &[&str]) -> LocationDetail {
+        LocationDetail {
+            area: Some(area.iter().map(|a| a.to_string()).collect()),
+            display_name: None,
+        }
+    }
+
+    #[test]
+    fn it_walks_the_hierarchy() {
+        let uk = Location::new("UK");
+        assert_eq!(uk.depth(), 0);
+        assert!(uk.parent().is_none());
+
+        let surrey = uk
+            .child("South East England")
+            .and_then(|l| l.child("Surrey"))
+            .unwrap();
+        assert_eq!(surrey.depth(), 2);
+        assert_eq!(surrey.name(), "Surrey");
+        assert_eq!(surrey.country(), "UK");
+        assert_eq!(surrey.to_string(), "UK > South East England > Surrey");
+        assert_eq!(surrey.parent().unwrap().name(), "South East England");
+        assert!(uk.contains(&surrey));
+        assert!(!surrey.contains(&uk));
+    }
+
+    #[test]
+    fn it_limits_depth() {
+        let mut location = Location::new("US");
+        for i in 0..7 {
+            location = location.child(&i.to_string()).unwrap();
+        }
+        assert_eq!(location.depth(), 7);
+        assert!(location.child("too deep").is_none());
+    }
+
+    #[test]
+    fn it_converts_from_location_detail() {
+        let location = Location::try_from(&detail(&["US", "Texas", "Austin"])).unwrap();
+        assert_eq!(location.area(), ["US", "Texas", "Austin"]);
+        assert_eq!(
+            Location::try_from(&LocationDetail::default()),
+            Err(LocationError::Empty)
+        );
+        assert_eq!(
+            Location::try_from(&detail(&["a"; 9])),
+            Err(LocationError::TooDeep(9))
+        );
+    }
+
+    #[test]
+    fn it_fills_location_parameters() {
+        let client = Client::new("id".into(), "key".into());
+        let location = Location::try_from(&detail(&["US", "Texas", "Austin"])).unwrap();
+        let search = client.search().location("ignored").location_path(&location);
+        assert_eq!(search.get_parameters().locations, location.area());
+    }
+}
@@ -0,0 +1,160 @@
+#[cfg(test)]
+mod tests {
+    use adzuna::config::Defaults;
+    use adzuna::models::{Country, Location, TopCompanies};
+    use adzuna::transport::StubTransport;
+    use adzuna::Client;
+    use serde_json::{json, Value};
+    use std::sync::Arc;
+
+    fn job(id: &str, tag: &str, city: &str, salary: f64) -> Value {
+        json!({
+            "id": id,
+            "created": "2024-01-01T00:00:00Z",
+            "title": "Engineer",
+            "description": "",
+            "redirect_url": "",
+            "latitude": 0.0,
+            "longitude": 0.0,
+            "category": { "tag": tag, "label": tag },
+            "location": { "area": ["UK", city], "display_name": city },
+            "salary_min": salary,
+            "salary_max": salary,
+            "salary_is_predicted": "0",
+            "company": { "display_name": "Acme Ltd" },
+            "adref": ""
+        })
+    }
+
+    #[test]
+    fn it_parses_decimal_average_salaries() {
+        let top: TopCompanies = serde_json::from_value(json!({
+            "leaderboard": [{ "canonical_name": "acme", "count": 3, "average_salary": 41234.56 }]
+        }))
+        .unwrap();
+        assert_eq!(top.leaderboard.unwrap()[0].average_salary, Some(41234.56));
+    }
+
+    #[tokio::test]
+    async fn it_builds_company_profiles() {
+        let stub = StubTransport::new()
+            .respond_json(
+                "/jobs/gb/top_companies",
+                &json!({ "leaderboard": [
+                    { "canonical_name": "globex", "count": 90 },
+                    { "canonical_name": "acme", "display_name": "Acme", "count": 40, "average_salary": 45000.5 }
+                ] }),
+            )
+            .respond_json(
+                "/jobs/gb/search/1",
+                &json!({
+                    "results": [
+                        job("1", "it-jobs", "London", 40000.0),
+                        job("2", "it-jobs", "Leeds", 50000.0),
+                        job("3", "sales-jobs", "London", 60000.0)
+                    ],
+                    "count": 3,
+                    "mean": 50000.0
+                }),
+            );
+        let client = Client::new("id".into(), "key".into()).transport(stub);
+        let profile = client
+            .company_profile("acme")
+            .country(Country::UnitedKingdom)
+            .fetch()
+            .await
+            .unwrap();
+
+        assert_eq!(profile.display_name.as_deref(), Some("Acme"));
+        assert_eq!(profile.rank, Some(2));
+        assert_eq!(profile.leaderboard_count, Some(40));
+        assert_eq!(profile.leaderboard_average_salary, Some(45000.5));
+        assert_eq!(profile.job_count, 3);
+        assert_eq!(profile.salaries.unwrap().median, 50000.0);
+        assert_eq!(profile.categories["it-jobs"], 2);
+        assert_eq!(profile.locations["London"], 2);
+    }
+
+    #[tokio::test]
+    async fn it_clamps_paging_settings() {
+        let stub = Arc::new(
+            StubTransport::new()
+                .respond_json("/jobs/gb/top_companies", &json!({ "leaderboard": [] }))
+                .respond_json(
+                    "/jobs/gb/search/1",
+                    &json!({ "results": [job("1", "it-jobs", "Leeds", 30000.0)], "count": 9, "mean": 0.0 }),
+                ),
+        );
+        let client = Client::new("id".into(), "key".into()).transport(stub.clone());
+        let profile = client
+            .company_profile("acme")
+            .country(Country::UnitedKingdom)
+            .max_pages(0)
+            .results_per_page(0)
+            .fetch()
+            .await
+            .unwrap();
+        assert_eq!(profile.sampled_jobs, 1);
+
+        let searches: Vec<_> = stub
+            .requests()
+            .into_iter()
+            .filter(|request| request.url.contains("/search/"))
+            .collect();
+        assert_eq!(searches.len(), 1);
+        assert!(searches[0]
+            .query
+            .contains(&("results_per_page".into(), "1".into())));
+
+        client
+            .company_profile("acme")
+            .country(Country::UnitedKingdom)
+            .results_per_page(500)
+            .fetch()
+            .await
+            .unwrap();
+        let last = stub
+            .requests()
+            .into_iter()
+            .rev()
+            .find(|request| request.url.contains("/search/"))
+            .unwrap();
+        assert!(last
+            .query
+            .contains(&("results_per_page".into(), "50".into())));
+    }
+
+    #[tokio::test]
+    async fn it_ignores_default_filters_unless_set() {
+        let stub = Arc::new(
+            StubTransport::new()
+                .respond_json("/jobs/gb/top_companies", &json!({ "leaderboard": [] }))
+                .respond_json(
+                    "/jobs/gb/search/1",
+                    &json!({ "results": [], "count": 0, "mean": 0.0 }),
+                ),
+        );
+        let client = Client::new("id".into(), "key".into())
+            .transport(stub.clone())
+            .defaults(Defaults {
+                category: Some("sales-jobs".into()),
+                location: Some(Location::new("UK").child("Leeds").unwrap()),
+                ..Default::default()
+            });
+        client
+            .company_profile("acme")
+            .country(Country::UnitedKingdom)
+            .fetch()
+            .await
+            .unwrap();
+
+        let requests = stub.requests();
+        assert_eq!(requests.len(), 2);
+        for request in requests {
+            assert!(!request
+                .query
+                .iter()
+                .any(|(key, _)| key == "category" || key.starts_with("location")));
+        }
+    }
+}
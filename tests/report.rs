@@ -0,0 +1,105 @@
+#[cfg(test)]
+mod tests {
+    use adzuna::config::Defaults;
+    use adzuna::models::{Category, Country, Location};
+    use adzuna::transport::StubTransport;
+    use adzuna::Client;
+    use serde_json::json;
+    use std::sync::Arc;
+
+    fn category(tag: &str) -> Category {
+        Category {
+            tag: tag.to_string(),
+            label: tag.to_uppercase(),
+        }
+    }
+
+    #[tokio::test]
+    async fn it_compares_categories_and_locations() {
+        let stub = StubTransport::new()
+            .respond_json(
+                "/jobs/gb/histogram",
+                &json!({ "histogram": { "20000": 10, "30000": 10 } }),
+            )
+            .respond_json(
+                "/jobs/gb/history",
+                &json!({ "month": { "2024-01": 30000.0, "2024-02": 31000.0 } }),
+            );
+        let client = Client::new("id".into(), "key".into()).transport(stub);
+        let report = client
+            .salary_report()
+            .country(Country::UnitedKingdom)
+            .categories(&[category("it-jobs"), category("sales-jobs")])
+            .location(&Location::new("UK").child("London").unwrap())
+            .build()
+            .await;
+
+        assert_eq!(report.rows.len(), 2);
+        let row = &report.rows[1];
+        assert_eq!(row.category.as_deref(), Some("sales-jobs"));
+        assert_eq!(row.location.as_deref(), Some("UK > London"));
+        assert_eq!(row.sample_size, 20);
+        assert_eq!(row.median, Some(30000.0));
+        assert_eq!(row.latest_average, Some(31000.0));
+        assert_eq!(row.trend_per_month, Some(1000.0));
+        assert!(row.error.is_none());
+
+        let mut csv = Vec::new();
+        report.write_csv(&mut csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        assert_eq!(csv.lines().count(), 3);
+        assert!(csv.starts_with("category,category_label,location,sample_size,"));
+
+        let mut json = Vec::new();
+        report.write_json(&mut json).unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&json).unwrap();
+        assert_eq!(json["country"], "gb");
+        assert_eq!(json["rows"][0]["category"], "it-jobs");
+    }
+
+    #[tokio::test]
+    async fn it_keeps_failed_rows() {
+        let stub =
+            StubTransport::new().respond_json("/jobs/us/histogram", &json!({ "histogram": {} }));
+        let client = Client::new("id".into(), "key".into()).transport(stub);
+        let report = client
+            .salary_report()
+            .country(Country::UnitedStates)
+            .build()
+            .await;
+
+        assert_eq!(report.rows.len(), 1);
+        assert_eq!(report.rows[0].category, None);
+        assert!(report.rows[0].error.is_some());
+    }
+
+    #[tokio::test]
+    async fn it_ignores_default_filters_for_unset_rows() {
+        let stub = Arc::new(
+            StubTransport::new()
+                .respond_json("/jobs/gb/histogram", &json!({ "histogram": {} }))
+                .respond_json("/jobs/gb/history", &json!({ "month": {} })),
+        );
+        let client = Client::new("id".into(), "key".into())
+            .transport(stub.clone())
+            .defaults(Defaults {
+                category: Some("sales-jobs".into()),
+                location: Some(Location::new("UK").child("Leeds").unwrap()),
+                ..Default::default()
+            });
+        client
+            .salary_report()
+            .country(Country::UnitedKingdom)
+            .build()
+            .await;
+
+        let requests = stub.requests();
+        assert_eq!(requests.len(), 2);
+        for request in requests {
+            assert!(!request
+                .query
+                .iter()
+                .any(|(key, _)| key == "category" || key.starts_with("location")));
+        }
+    }
+}
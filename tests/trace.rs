@@ -0,0 +1,95 @@
+#[cfg(all(test, feature = "tracing"))]
+mod tests {
+    use adzuna::credentials::{CredentialPool, Credentials};
+    use adzuna::models::Country;
+    use adzuna::transport::{HttpResponse, StubTransport};
+    use adzuna::Client;
+    use adzuna::RequestBuilder;
+    use reqwest::StatusCode;
+    use serde_json::json;
+    use std::collections::HashMap;
+    use std::fmt::Debug;
+    use std::sync::{Arc, Mutex};
+    use tracing::field::{Field, Visit};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::Subscriber;
+    use tracing_subscriber::layer::{Context, SubscriberExt};
+    use tracing_subscriber::registry::LookupSpan;
+    use tracing_subscriber::Layer;
+
+    type Fields = HashMap<String, String>;
+
+    /// Collects the fields of every closed span.
+    #[derive(Clone, Default)]
+    struct Capture {
+        open: Arc<Mutex<HashMap<u64, Fields>>>,
+        closed: Arc<Mutex<Vec<Fields>>>,
+    }
+
+    struct Visitor<'a>(&'a mut Fields);
+
+    impl Visit for Visitor<'_> {
+        fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+            self.0.insert(field.name().into(), format!("{value:?}"));
+        }
+    }
+
+    impl<S: Subscriber + for<'a> LookupSpan<'a>> Layer<S> for Capture {
+        fn on_new_span(&self, attributes: &Attributes<'_>, id: &Id, _: Context<'_, S>) {
+            let mut fields = Fields::new();
+            attributes.record(&mut Visitor(&mut fields));
+            self.open.lock().unwrap().insert(id.into_u64(), fields);
+        }
+
+        fn on_record(&self, id: &Id, values: &Record<'_>, _: Context<'_, S>) {
+            if let Some(fields) = self.open.lock().unwrap().get_mut(&id.into_u64()) {
+                values.record(&mut Visitor(fields));
+            }
+        }
+
+        fn on_close(&self, id: Id, _: Context<'_, S>) {
+            if let Some(fields) = self.open.lock().unwrap().remove(&id.into_u64()) {
+                self.closed.lock().unwrap().push(fields);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn it_records_request_spans() {
+        let capture = Capture::default();
+        let _guard =
+            tracing::subscriber::set_default(tracing_subscriber::registry().with(capture.clone()));
+
+        let stub = StubTransport::new()
+            .respond(
+                "/jobs/gb/search/3",
+                HttpResponse::new(StatusCode::TOO_MANY_REQUESTS, ""),
+            )
+            .respond_json(
+                "/jobs/gb/search/3",
+                &json!({ "results": [], "count": 0, "mean": 0.0 }),
+            );
+        let pool = CredentialPool::new(vec![
+            Credentials::new("first".into(), "a".into()),
+            Credentials::new("second".into(), "b".into()),
+        ]);
+        let client = Client::with_credentials(pool).transport(stub);
+        client
+            .search()
+            .country(Country::UnitedKingdom)
+            .page(3)
+            .fetch()
+            .await
+            .unwrap();
+
+        let spans = capture.closed.lock().unwrap();
+        assert_eq!(spans.len(), 1);
+        let span = &spans[0];
+        assert_eq!(span["endpoint"], "\"search\"");
+        assert_eq!(span["country"], "\"gb\"");
+        assert_eq!(span["page"], "3");
+        assert_eq!(span["attempt"], "2");
+        assert_eq!(span["status"], "200");
+        assert!(span.contains_key("latency_ms"));
+    }
+}
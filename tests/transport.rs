@@ -0,0 +1,262 @@
+#[cfg(test)]
+mod tests {
+    use adzuna::credentials::{CredentialPool, Credentials, Rotation};
+    use adzuna::middleware::{Middleware, SetHeader};
+    use adzuna::models::{Country, Location};
+    use adzuna::request::{Error, ErrorKind};
+    use adzuna::transport::{HttpRequest, HttpResponse, HttpTransport, StubTransport};
+    use adzuna::{Client, CountryRequest, RequestBuilder};
+    use async_trait::async_trait;
+    use reqwest::StatusCode;
+    use serde_json::json;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    fn stub_client(stub: StubTransport) -> (Client, Arc<StubTransport>) {
+        let stub = Arc::new(stub);
+        let client = Client::new("id".into(), "key".into()).transport(stub.clone());
+        (client, stub)
+    }
+
+    #[tokio::test]
+    async fn it_sends_parameters_and_decodes() {
+        let (client, stub) = stub_client(StubTransport::new().respond_json(
+            "/jobs/gb/search/2",
+            &json!({ "results": [], "count": 0, "mean": 0.0 }),
+        ));
+        let location = Location::new("UK").child("London").unwrap();
+        let jobs = client
+            .search()
+            .country(Country::UnitedKingdom)
+            .page(2)
+            .what("rust")
+            .location_path(&location)
+            .results_per_page(5)
+            .fetch()
+            .await
+            .unwrap();
+        assert_eq!(jobs.count, 0);
+
+        let request = &stub.requests()[0];
+        assert_eq!(
+            request.url,
+            "https://api.adzuna.com/v1/api/jobs/gb/search/2"
+        );
+        for pair in [
+            ("app_id", "id"),
+            ("what", "rust"),
+            ("location0", "UK"),
+            ("location1", "London"),
+            ("results_per_page", "5"),
+        ] {
+            assert!(request
+                .query
+                .contains(&(pair.0.to_string(), pair.1.to_string())));
+        }
+        assert!(!request.query.iter().any(|(key, _)| key == "category"));
+    }
+
+    #[tokio::test]
+    async fn it_reports_api_and_decode_errors() {
+        let (client, _) = stub_client(
+            StubTransport::new()
+                .respond(
+                    "/jobs/us/categories",
+                    HttpResponse::new(
+                        StatusCode::UNAUTHORIZED,
+                        json!({ "exception": "AUTH_FAIL", "doc": "", "display": "" }).to_string(),
+                    ),
+                )
+                .respond_json("/version", &json!({ "unexpected": true })),
+        );
+        let error = client.categories().fetch().await.unwrap_err();
+        assert_eq!(error.kind, ErrorKind::Status);
+        assert_eq!(error.http_status, 401);
+        assert_eq!(error.api_error.unwrap().exception, "AUTH_FAIL");
+
+        let error = client.api_version().fetch().await.unwrap_err();
+        assert_eq!(error.kind, ErrorKind::Decode);
+    }
+
+    #[tokio::test]
+    async fn it_keeps_partial_results_across_countries() {
+        let (client, _) = stub_client(StubTransport::new().respond_json(
+            "/jobs/gb/histogram",
+            &json!({ "histogram": { "20000": 3 } }),
+        ));
+        let histograms = client
+            .histogram()
+            .across(&[Country::UnitedKingdom, Country::France])
+            .fetch()
+            .await;
+        assert!(!histograms.is_complete());
+        assert!(histograms.results.contains_key(&Country::UnitedKingdom));
+        assert_eq!(histograms.errors[&Country::France].http_status, 404);
+    }
+
+    struct Recorder(Mutex<Vec<String>>);
+
+    impl Middleware for Recorder {
+        fn on_request(&self, request: &mut HttpRequest) {
+            request
+                .headers
+                .push(("X-Correlation-Id".into(), "abc".into()));
+            self.0.lock().unwrap().push("request".into());
+        }
+
+        fn on_response(
+            &self,
+            _request: &HttpRequest,
+            response: &mut Result<HttpResponse, Error>,
+            _elapsed: Duration,
+        ) {
+            if let Ok(response) = response {
+                response.body = json!({ "api_version": 1, "software_version": "patched" })
+                    .to_string()
+                    .into_bytes();
+            }
+            self.0.lock().unwrap().push("response".into());
+        }
+    }
+
+    #[tokio::test]
+    async fn it_runs_middleware() {
+        let stub = Arc::new(StubTransport::new().respond_json("/version", &json!({})));
+        let recorder = Arc::new(Recorder(Mutex::new(Vec::new())));
+        let client = Client::new("id".into(), "key".into())
+            .transport(stub.clone())
+            .middleware(SetHeader::new("User-Agent", "adzuna-tests"))
+            .middleware(recorder.clone());
+
+        let version = client.api_version().fetch().await.unwrap();
+        assert_eq!(version.software_version, "patched");
+        assert_eq!(*recorder.0.lock().unwrap(), ["request", "response"]);
+
+        let headers = &stub.requests()[0].headers;
+        assert!(headers.contains(&("User-Agent".into(), "adzuna-tests".into())));
+        assert!(headers.contains(&("X-Correlation-Id".into(), "abc".into())));
+    }
+
+    #[tokio::test]
+    async fn it_keeps_a_custom_transport_with_a_connect_timeout() {
+        let stub = Arc::new(StubTransport::new().respond_json(
+            "/version",
+            &json!({ "api_version": 1, "software_version": "1" }),
+        ));
+        let client = Client::new("id".into(), "key".into())
+            .transport(stub.clone())
+            .connect_timeout(Duration::from_secs(1));
+
+        client.api_version().fetch().await.unwrap();
+        assert_eq!(stub.requests().len(), 1);
+    }
+
+    /// Never answers in time, and ignores the request timeout.
+    #[derive(Default)]
+    struct SlowTransport(Mutex<Vec<Option<Duration>>>);
+
+    #[async_trait]
+    impl HttpTransport for SlowTransport {
+        async fn send(&self, request: HttpRequest) -> Result<HttpResponse, Error> {
+            self.0.lock().unwrap().push(request.timeout);
+            tokio::time::sleep(Duration::from_secs(60)).await;
+            Err(Error::from_kind(ErrorKind::Transport))
+        }
+    }
+
+    #[tokio::test]
+    async fn it_enforces_the_timeout_over_any_transport() {
+        let transport = Arc::new(SlowTransport::default());
+        let client = Client::new("id".into(), "key".into())
+            .transport(transport.clone())
+            .timeout(Duration::from_millis(10));
+
+        let error = client.api_version().fetch().await.unwrap_err();
+        assert_eq!(error.kind, ErrorKind::Timeout);
+        assert_eq!(
+            transport.0.lock().unwrap()[0],
+            Some(Duration::from_millis(10))
+        );
+    }
+
+    #[tokio::test]
+    async fn it_lets_a_request_override_the_client_timeout() {
+        let transport = Arc::new(SlowTransport::default());
+        let client = Client::new("id".into(), "key".into())
+            .transport(transport.clone())
+            .timeout(Duration::from_secs(30));
+
+        let error = client
+            .categories()
+            .timeout(Duration::from_millis(10))
+            .fetch()
+            .await
+            .unwrap_err();
+        assert_eq!(error.kind, ErrorKind::Timeout);
+        assert_eq!(
+            transport.0.lock().unwrap()[0],
+            Some(Duration::from_millis(10))
+        );
+    }
+
+    #[tokio::test]
+    async fn it_tracks_usage() {
+        let (client, _) = stub_client(
+            StubTransport::new().respond_json("/jobs/gb/categories", &json!({ "results": [] })),
+        );
+        client
+            .categories()
+            .country(Country::UnitedKingdom)
+            .fetch()
+            .await
+            .unwrap();
+        client.categories().fetch().await.unwrap_err();
+
+        let usage = client.usage_stats();
+        assert_eq!(usage.total().requests, 2);
+        assert_eq!(usage.endpoint("categories").error_count(), 1);
+        assert_eq!(usage.country("gb").errors.len(), 0);
+        assert_eq!(usage.country("gb").bytes_received, 14);
+        assert_eq!(usage.country("us").errors[&ErrorKind::Status], 1);
+
+        client.reset_usage_stats();
+        assert_eq!(client.usage_stats().total().requests, 0);
+    }
+
+    #[tokio::test]
+    async fn it_rotates_credentials_on_rate_limits() {
+        let stub = Arc::new(
+            StubTransport::new()
+                .respond(
+                    "/version",
+                    HttpResponse::new(StatusCode::TOO_MANY_REQUESTS, ""),
+                )
+                .respond_json(
+                    "/version",
+                    &json!({ "api_version": 1, "software_version": "1" }),
+                ),
+        );
+        let pool = CredentialPool::new(vec![
+            Credentials::new("first".into(), "a".into()),
+            Credentials::new("second".into(), "b".into()),
+        ])
+        .rotation(Rotation::OnFailure);
+        let client = Client::with_credentials(pool).transport(stub.clone());
+
+        client.api_version().fetch().await.unwrap();
+        client.api_version().fetch().await.unwrap();
+
+        let app_ids: Vec<String> = stub
+            .requests()
+            .iter()
+            .map(|request| request.query[0].1.clone())
+            .collect();
+        assert_eq!(app_ids, ["first", "second", "second"]);
+
+        let usage = client.credentials().usage();
+        assert!(usage[0].disabled);
+        assert_eq!(usage[0].rate_limited, 1);
+        assert_eq!(usage[1].requests, 2);
+        assert!(!usage[1].disabled);
+    }
+}
@@ -0,0 +1,138 @@
+#[cfg(test)]
+mod tests {
+    use adzuna::transport::{HttpResponse, StubTransport};
+    use adzuna::watcher::WatchError;
+    use adzuna::watcher::{JobWatcher, MemoryStore};
+    use adzuna::Client;
+    use futures::StreamExt;
+    use reqwest::StatusCode;
+    use serde_json::{json, Value};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    fn job(id: &str, created: &str) -> Value {
+        json!({
+            "id": id,
+            "created": created,
+            "title": "Rust Developer",
+            "description": "",
+            "redirect_url": "",
+            "latitude": 0.0,
+            "longitude": 0.0,
+            "category": { "tag": "it-jobs", "label": "IT Jobs" },
+            "location": { "area": ["UK"], "display_name": "UK" },
+            "salary_min": 0.0,
+            "salary_max": 0.0,
+            "salary_is_predicted": "0",
+            "company": {},
+            "adref": ""
+        })
+    }
+
+    fn page(jobs: Vec<Value>) -> Value {
+        json!({ "results": jobs, "count": 0, "mean": 0.0 })
+    }
+
+    fn stub() -> StubTransport {
+        StubTransport::new()
+            .respond_json(
+                "/search/1",
+                &page(vec![
+                    job("b", "2024-01-02T00:00:00Z"),
+                    job("a", "2024-01-01T00:00:00Z"),
+                ]),
+            )
+            .respond_json(
+                "/search/1",
+                &page(vec![
+                    job("c", "2024-01-03T00:00:00Z"),
+                    job("b", "2024-01-02T00:00:00Z"),
+                    job("a", "2024-01-01T00:00:00Z"),
+                ]),
+            )
+            .respond_json("/search/2", &page(vec![]))
+    }
+
+    fn ids(jobs: &[adzuna::models::Job]) -> Vec<&str> {
+        jobs.iter().map(|job| job.id.as_str()).collect()
+    }
+
+    #[tokio::test]
+    async fn it_reports_only_new_jobs_across_restarts() {
+        let stub = Arc::new(stub());
+        let client = Client::new("id".into(), "key".into()).transport(stub.clone());
+        let store = Arc::new(MemoryStore::new());
+
+        let mut watcher = JobWatcher::new(client.search().what("rust"), store.clone());
+        assert_eq!(ids(&watcher.poll().await.unwrap()), ["b", "a"]);
+        let query = &stub.requests()[0].query;
+        assert!(query.contains(&("sort_by".into(), "date".into())));
+        assert!(query.contains(&("sort_dir".into(), "down".into())));
+        assert_eq!(ids(&watcher.poll().await.unwrap()), ["c"]);
+
+        let mut restarted = JobWatcher::new(client.search().what("rust"), store.clone());
+        assert!(restarted.poll().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn it_streams_new_jobs() {
+        let client = Client::new("id".into(), "key".into()).transport(stub());
+        let watcher = JobWatcher::new(client.search(), MemoryStore::new())
+            .skip_existing()
+            .interval(Duration::from_millis(1));
+        let jobs: Vec<_> = watcher.stream().take(1).collect().await;
+        assert_eq!(jobs[0].as_ref().unwrap().id, "c");
+    }
+
+    #[tokio::test]
+    async fn it_reports_jobs_again_after_a_failed_poll() {
+        let stub = StubTransport::new()
+            .respond_json(
+                "/search/1",
+                &page(vec![
+                    job("b", "2024-01-02T00:00:00Z"),
+                    job("a", "2024-01-01T00:00:00Z"),
+                ]),
+            )
+            .respond(
+                "/search/2",
+                HttpResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "{}"),
+            )
+            .respond_json("/search/2", &page(vec![]));
+        let client = Client::new("id".into(), "key".into()).transport(stub);
+        let store = Arc::new(MemoryStore::new());
+
+        let mut watcher = JobWatcher::new(client.search(), store.clone());
+        assert!(matches!(watcher.poll().await, Err(WatchError::Request(_))));
+        assert_eq!(ids(&watcher.poll().await.unwrap()), ["b", "a"]);
+
+        let mut restarted = JobWatcher::new(client.search(), store);
+        assert!(restarted.poll().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn it_catches_up_on_jobs_beyond_max_pages() {
+        let stub = StubTransport::new()
+            .respond_json("/search/1", &page(vec![job("a", "2024-01-01T00:00:00Z")]))
+            .respond_json(
+                "/search/1",
+                &page(vec![
+                    job("d", "2024-01-04T00:00:00Z"),
+                    job("c", "2024-01-03T00:00:00Z"),
+                ]),
+            )
+            .respond_json(
+                "/search/2",
+                &page(vec![
+                    job("b", "2024-01-02T00:00:00Z"),
+                    job("a", "2024-01-01T00:00:00Z"),
+                ]),
+            );
+        let client = Client::new("id".into(), "key".into()).transport(stub);
+
+        let mut watcher = JobWatcher::new(client.search(), MemoryStore::new()).max_pages(1);
+        assert_eq!(ids(&watcher.poll().await.unwrap()), ["a"]);
+        assert_eq!(ids(&watcher.poll().await.unwrap()), ["d", "c"]);
+        assert_eq!(ids(&watcher.poll().await.unwrap()), ["b"]);
+    }
+}